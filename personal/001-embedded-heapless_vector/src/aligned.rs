@@ -0,0 +1,157 @@
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use crate::{ArrayVec, ArrayVecIntoIter, LenType};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+// Alignment marker for AlignedArrayVec. Sealed: use one of the `AlignN`
+// types below.
+pub trait Alignment: sealed::Sealed + Copy {}
+
+macro_rules! alignments {
+    ($($name:ident => $align:literal),* $(,)?) => {$(
+        /// Zero-sized alignment marker:
+        #[doc = concat!("storage starts on a ", stringify!($align), "-byte boundary.")]
+        #[derive(Debug, Clone, Copy, Default)]
+        #[repr(align($align))]
+        pub struct $name;
+
+        impl sealed::Sealed for $name {}
+        impl Alignment for $name {}
+    )*};
+}
+
+alignments! {
+    Align8 => 8,
+    Align16 => 16,
+    Align32 => 32,
+    Align64 => 64,
+    Align128 => 128,
+}
+
+// ArrayVec whose element storage starts at (at least) `A`'s alignment,
+// for DMA engines and SIMD loads. The `[A; 0]` field takes no space but
+// raises the struct's alignment; since both structs are `repr(C)` with
+// the storage first, `values` sits at offset 0 and inherits it.
+//
+// Derefs to the inner ArrayVec, so the full push/pop/slice API works
+// unchanged.
+#[repr(C)]
+pub struct AlignedArrayVec<T, const N: usize, A: Alignment, L: LenType = usize> {
+    _align: [A; 0],
+    inner: ArrayVec<T, N, L>,
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> AlignedArrayVec<T, N, A, L> {
+    /// Creates a new empty AlignedArrayVec.
+    pub const fn new() -> Self {
+        AlignedArrayVec {
+            _align: [],
+            inner: ArrayVec::new(),
+        }
+    }
+
+    /// Moves the elements of `inner` into aligned storage.
+    pub const fn from_array_vec(inner: ArrayVec<T, N, L>) -> Self {
+        AlignedArrayVec { _align: [], inner }
+    }
+
+    /// Unwraps into a plain ArrayVec (losing the alignment guarantee).
+    pub fn into_inner(self) -> ArrayVec<T, N, L> {
+        self.inner
+    }
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> Deref for AlignedArrayVec<T, N, A, L> {
+    type Target = ArrayVec<T, N, L>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+// Swapping the inner ArrayVec out through this is fine: whatever is put
+// back lives at the same (aligned) address.
+impl<T, const N: usize, A: Alignment, L: LenType> DerefMut for AlignedArrayVec<T, N, A, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> Default for AlignedArrayVec<T, N, A, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> Clone for AlignedArrayVec<T, N, A, L>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::from_array_vec(self.inner.clone())
+    }
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> From<ArrayVec<T, N, L>>
+    for AlignedArrayVec<T, N, A, L>
+{
+    fn from(inner: ArrayVec<T, N, L>) -> Self {
+        Self::from_array_vec(inner)
+    }
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> fmt::Debug for AlignedArrayVec<T, N, A, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> FromIterator<T> for AlignedArrayVec<T, N, A, L> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_array_vec(ArrayVec::from_iter(iter))
+    }
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> Extend<T> for AlignedArrayVec<T, N, A, L> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.inner.extend(iter);
+    }
+}
+
+impl<T, const N: usize, A: Alignment, L: LenType> IntoIterator for AlignedArrayVec<T, N, A, L> {
+    type Item = T;
+    type IntoIter = ArrayVecIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'a, T, const N: usize, A: Alignment, L: LenType> IntoIterator
+    for &'a AlignedArrayVec<T, N, A, L>
+{
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.iter()
+    }
+}
+
+impl<'a, T, const N: usize, A: Alignment, L: LenType> IntoIterator
+    for &'a mut AlignedArrayVec<T, N, A, L>
+{
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.iter_mut()
+    }
+}
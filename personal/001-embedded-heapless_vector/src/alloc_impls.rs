@@ -0,0 +1,26 @@
+// Vec conversions, only built with the `alloc` cargo feature (which
+// `std` turns on).
+use alloc::vec::Vec;
+
+use crate::{ArrayVec, CapacityError, LenType};
+
+impl<T, const N: usize, L: LenType> From<ArrayVec<T, N, L>> for Vec<T> {
+    fn from(arr_vec: ArrayVec<T, N, L>) -> Self {
+        let mut vec = Vec::with_capacity(arr_vec.len());
+        vec.extend(arr_vec);
+        vec
+    }
+}
+
+// Moves the elements out of a Vec, handing the Vec back untouched if it
+// has more than `N`.
+impl<T, const N: usize, L: LenType> TryFrom<Vec<T>> for ArrayVec<T, N, L> {
+    type Error = CapacityError<Vec<T>>;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        if vec.len() > N {
+            return Err(CapacityError::new(vec));
+        }
+        Ok(vec.into_iter().collect())
+    }
+}
@@ -0,0 +1,413 @@
+use core::fmt;
+use core::iter::{Chain, FusedIterator};
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::slice;
+
+use crate::CapacityError;
+
+// Fixed-capacity ring buffer (double-ended queue) on the same
+// `[MaybeUninit<T>; N]` storage as ArrayVec. O(1) push/pop at both ends.
+//
+// Invariant: the `len` slots starting at `head` (wrapping around at `N`)
+// are init; every other slot is uninit.
+pub struct ArrayDeque<T, const N: usize> {
+    values: [MaybeUninit<T>; N],
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayDeque<T, N> {
+    /// Creates a new empty ArrayDeque.
+    pub const fn new() -> Self {
+        ArrayDeque {
+            values: [const { MaybeUninit::uninit() }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if no more elements fit.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    // Physical slot of the `i`th element. `head < N` and `i <= N`, so a
+    // single subtraction wraps it (no `%`, which would panic for N = 0).
+    fn slot(&self, i: usize) -> usize {
+        let idx = self.head + i;
+        if idx >= N { idx - N } else { idx }
+    }
+
+    /// Pushes to the back if there's space, handing `value` back in a
+    /// `CapacityError` if full.
+    pub fn try_push_back(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError::new(value));
+        }
+        let slot = self.slot(self.len);
+        self.values[slot].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Pushes to the front if there's space, handing `value` back in a
+    /// `CapacityError` if full.
+    pub fn try_push_front(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError::new(value));
+        }
+        self.head = if self.head == 0 { N - 1 } else { self.head - 1 };
+        self.values[self.head].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Overwrite-oldest mode for rolling logs: pushes to the back, and if
+    /// full, first evicts and returns the front element. With `N == 0`
+    /// the value itself is handed straight back.
+    pub fn push_back_overwrite(&mut self, value: T) -> Option<T> {
+        if N == 0 {
+            return Some(value);
+        }
+        let evicted = if self.len == N {
+            self.pop_front()
+        } else {
+            None
+        };
+        // Can't fail: there's room now.
+        let _ = self.try_push_back(value);
+        evicted
+    }
+
+    /// Removes and returns the front element (or `None` if empty).
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.head;
+        self.head = self.slot(1);
+        self.len -= 1;
+        // SAFETY: `slot` was the first init slot; it's now outside the
+        // init range, so it's read exactly once.
+        Some(unsafe { self.values[slot].assume_init_read() })
+    }
+
+    /// Removes and returns the back element (or `None` if empty).
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let slot = self.slot(self.len);
+        // SAFETY: Same as `pop_front`, at the other end.
+        Some(unsafe { self.values[slot].assume_init_read() })
+    }
+
+    /// Returns a reference to the `index`th element from the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: `index < len`, so the slot is init.
+        Some(unsafe { self.values[self.slot(index)].assume_init_ref() })
+    }
+
+    /// Returns a mutable reference to the `index`th element from the front.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let slot = self.slot(index);
+        Some(unsafe { self.values[slot].assume_init_mut() })
+    }
+
+    /// Returns the front element, if any.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the back element, if any.
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    // Physical `(start, len)` of the two contiguous runs: `head..` up to
+    // the end of the array, then the wrapped part from slot 0.
+    fn runs(&self) -> ((usize, usize), usize) {
+        let first = self.len.min(N - self.head);
+        ((self.head, first), self.len - first)
+    }
+
+    /// Returns the elements as two slices, front to back. The second is
+    /// non-empty only when the elements wrap around the end of storage.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let ((start, first), second) = self.runs();
+        let base = self.values.as_ptr() as *const T;
+        // SAFETY: Both runs are init and don't overlap.
+        unsafe {
+            (
+                core::slice::from_raw_parts(base.add(start), first),
+                core::slice::from_raw_parts(base, second),
+            )
+        }
+    }
+
+    /// Mutable version of `as_slices`.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let ((start, first), second) = self.runs();
+        let base = self.values.as_mut_ptr() as *mut T;
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(base.add(start), first),
+                core::slice::from_raw_parts_mut(base, second),
+            )
+        }
+    }
+
+    /// Iterates front to back.
+    pub fn iter(&self) -> ArrayDequeIter<'_, T> {
+        let (a, b) = self.as_slices();
+        ArrayDequeIter {
+            inner: a.iter().chain(b.iter()),
+        }
+    }
+
+    /// Iterates front to back, mutably.
+    pub fn iter_mut(&mut self) -> ArrayDequeIterMut<'_, T> {
+        let (a, b) = self.as_mut_slices();
+        ArrayDequeIterMut {
+            inner: a.iter_mut().chain(b.iter_mut()),
+        }
+    }
+
+    /// Drops all elements, leaving the deque empty.
+    /// SAFETY: `len` is reset *before* dropping, and the back run is
+    /// dropped by a guard, so a panicking `Drop` in the front run
+    /// neither double drops nor leaks the back run.
+    pub fn clear(&mut self) {
+        struct Dropper<T>(*mut [T]);
+
+        impl<T> Drop for Dropper<T> {
+            fn drop(&mut self) {
+                unsafe { ptr::drop_in_place(self.0) }
+            }
+        }
+
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (front as *mut [T], back as *mut [T]);
+        self.head = 0;
+        self.len = 0;
+        let _back = Dropper(back);
+        unsafe { ptr::drop_in_place(front) };
+    }
+}
+
+// Borrowing iterators: the two slice iterators chained together, behind
+// named types so the chain stays an implementation detail.
+pub struct ArrayDequeIter<'a, T> {
+    inner: Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>,
+}
+
+pub struct ArrayDequeIterMut<'a, T> {
+    inner: Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>,
+}
+
+impl<'a, T> Iterator for ArrayDequeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for ArrayDequeIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for ArrayDequeIter<'_, T> {}
+
+impl<T> FusedIterator for ArrayDequeIter<'_, T> {}
+
+impl<T> Clone for ArrayDequeIter<'_, T> {
+    fn clone(&self) -> Self {
+        ArrayDequeIter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for ArrayDequeIter<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayDequeIter").field(&self.inner).finish()
+    }
+}
+
+impl<'a, T> Iterator for ArrayDequeIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for ArrayDequeIterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for ArrayDequeIterMut<'_, T> {}
+
+impl<T> FusedIterator for ArrayDequeIterMut<'_, T> {}
+
+impl<T> fmt::Debug for ArrayDequeIterMut<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayDequeIterMut")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+impl<T, const N: usize> Default for ArrayDeque<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Drop the init elements, including any that wrapped around.
+impl<T, const N: usize> Drop for ArrayDeque<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Clone for ArrayDeque<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut deque = Self::new();
+        for item in self {
+            // Can't fail: same capacity.
+            let _ = deque.try_push_back(item.clone());
+        }
+        deque
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for ArrayDeque<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, U, const N: usize, const M: usize> PartialEq<ArrayDeque<U, M>> for ArrayDeque<T, N>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &ArrayDeque<U, M>) -> bool {
+        self.len == other.len && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T, const N: usize> Eq for ArrayDeque<T, N> where T: Eq {}
+
+// Consuming iterator: pops from either end of the owned deque.
+pub struct ArrayDequeIntoIter<T, const N: usize> {
+    deque: ArrayDeque<T, N>,
+}
+
+impl<T, const N: usize> Iterator for ArrayDequeIntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for ArrayDequeIntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for ArrayDequeIntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for ArrayDequeIntoIter<T, N> {}
+
+impl<T, const N: usize> fmt::Debug for ArrayDequeIntoIter<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayDequeIntoIter")
+            .field(&self.deque)
+            .finish()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArrayDeque<T, N> {
+    type Item = T;
+    type IntoIter = ArrayDequeIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayDequeIntoIter { deque: self }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayDeque<T, N> {
+    type Item = &'a T;
+    type IntoIter = ArrayDequeIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayDeque<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = ArrayDequeIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,228 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+use crate::{ArrayVec, CapacityError};
+
+// Fixed-capacity UTF-8 string built on ArrayVec<u8, N>.
+// Invariant: the init bytes (the first `len` slots) are always valid
+// UTF-8, so every method that adds or removes bytes works in whole
+// `char`s.
+pub struct ArrayString<const N: usize> {
+    vec: ArrayVec<u8, N>,
+}
+
+impl<const N: usize> ArrayString<N> {
+    /// Creates a new empty ArrayString.
+    pub fn new() -> Self {
+        ArrayString {
+            vec: ArrayVec::new(),
+        }
+    }
+
+    /// Returns the length in bytes (not chars).
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns `true` if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns the capacity in bytes.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the init bytes as a `&str`.
+    /// SAFETY: Unsafe internally, but safe API: init bytes are UTF-8.
+    pub fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(self.vec.as_slice()) }
+    }
+
+    /// Returns the init bytes as a `&mut str`.
+    /// `&mut str` can only be mutated in ways that keep it UTF-8.
+    pub fn as_mut_str(&mut self) -> &mut str {
+        unsafe { core::str::from_utf8_unchecked_mut(self.vec.as_mut_slice()) }
+    }
+
+    /// Appends `s` if it fits entirely, handing it back in a
+    /// `CapacityError` otherwise.
+    /// Never writes a partial string.
+    pub fn try_push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+        let len = self.vec.len();
+        if s.len() > N - len {
+            return Err(CapacityError::new(s));
+        }
+        // SAFETY: Checked above that `[len, len + s.len())` is in bounds;
+        // those slots are uninit, so the copy can't overlap `s`.
+        unsafe {
+            let dst = self.vec.values.as_mut_ptr().add(len) as *mut u8;
+            ptr::copy_nonoverlapping(s.as_ptr(), dst, s.len());
+        }
+        self.vec.store_len(len + s.len());
+        Ok(())
+    }
+
+    /// Appends a char if its UTF-8 encoding fits, handing it back in a
+    /// `CapacityError` otherwise.
+    pub fn try_push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        let mut buf = [0; 4];
+        self.try_push_str(c.encode_utf8(&mut buf))
+            .map_err(|_| CapacityError::new(c))
+    }
+
+    /// Removes the last char and returns it (or `None` if empty).
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.vec.store_len(self.len() - c.len_utf8());
+        Some(c)
+    }
+
+    /// Shortens the string to `new_len` bytes. Does nothing if `new_len`
+    /// is greater than the current length.
+    /// Panics if `new_len` does not lie on a char boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+        assert!(
+            self.as_str().is_char_boundary(new_len),
+            "truncate index (is {new_len}) should be on a char boundary"
+        );
+        // `u8` has no `Drop`, so this only lowers `len`.
+        self.vec.truncate(new_len);
+    }
+
+    /// Empties the string.
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+}
+
+impl<const N: usize> Default for ArrayString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Clone for ArrayString<N> {
+    fn clone(&self) -> Self {
+        let mut s = Self::new();
+        // Can't fail: same capacity.
+        let _ = s.try_push_str(self.as_str());
+        s
+    }
+}
+
+// Fallible conversion: hands the input back if it doesn't fit.
+impl<'a, const N: usize> TryFrom<&'a str> for ArrayString<N> {
+    type Error = CapacityError<&'a str>;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        let mut string = Self::new();
+        string.try_push_str(s)?;
+        Ok(string)
+    }
+}
+
+impl<const N: usize> Deref for ArrayString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> DerefMut for ArrayString<N> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for ArrayString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayString<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+// Lets maps keyed by ArrayString be looked up with a plain `&str`.
+impl<const N: usize> Borrow<str> for ArrayString<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// Implement fmt::Write so `write!`/`writeln!` format straight into the
+// inline buffer. Overflow surfaces as `fmt::Error`; whatever was written
+// before the failing piece stays in the string.
+impl<const N: usize> fmt::Write for ArrayString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.try_push(c).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> fmt::Display for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<ArrayString<M>> for ArrayString<N> {
+    fn eq(&self, other: &ArrayString<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for ArrayString<N> {}
+
+impl<const N: usize> PartialEq<str> for ArrayString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for ArrayString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> PartialOrd for ArrayString<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for ArrayString<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+// Hashes like `str`, which `Borrow<str>` requires.
+impl<const N: usize> Hash for ArrayString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
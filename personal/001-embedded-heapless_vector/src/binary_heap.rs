@@ -0,0 +1,275 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use crate::{ArrayVec, CapacityError};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+// Which end of the ordering a BinaryHeap pops first.
+pub trait HeapKind: sealed::Sealed {
+    /// How an element must compare to another to be popped before it.
+    const POPS_FIRST: Ordering;
+}
+
+// Max-heap marker: the greatest element is popped first.
+pub enum Max {}
+
+// Min-heap marker: the least element is popped first.
+pub enum Min {}
+
+impl sealed::Sealed for Max {}
+impl sealed::Sealed for Min {}
+
+impl HeapKind for Max {
+    const POPS_FIRST: Ordering = Ordering::Greater;
+}
+
+impl HeapKind for Min {
+    const POPS_FIRST: Ordering = Ordering::Less;
+}
+
+// Fixed-capacity priority queue: an implicit binary heap laid out in an
+// ArrayVec. `K` picks max-heap (the default) or min-heap order.
+//
+// Invariant: every element is popped no later than its children, i.e.
+// `data[(i - 1) / 2]` never compares behind `data[i]`.
+pub struct BinaryHeap<T, const N: usize, K: HeapKind = Max> {
+    data: ArrayVec<T, N>,
+    _kind: core::marker::PhantomData<K>,
+}
+
+impl<T, const N: usize, K: HeapKind> BinaryHeap<T, N, K> {
+    /// Creates a new empty BinaryHeap.
+    pub const fn new() -> Self {
+        BinaryHeap {
+            data: ArrayVec::new(),
+            _kind: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the heap is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the element that would be popped next, if any.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.get(0)
+    }
+
+    /// Returns the elements in heap (not sorted) order.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Iterates in heap (not sorted) order.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Drops all elements.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Unwraps into the backing ArrayVec, in heap order.
+    pub fn into_vec(self) -> ArrayVec<T, N> {
+        self.data
+    }
+}
+
+impl<T, const N: usize, K: HeapKind> BinaryHeap<T, N, K>
+where
+    T: Ord,
+{
+    // `true` if `a` should be popped before `b`.
+    fn before(a: &T, b: &T) -> bool {
+        a.cmp(b) == K::POPS_FIRST
+    }
+
+    /// Pushes a value if there's space, handing it back in a
+    /// `CapacityError` if full (same semantics as `ArrayVec::try_push`).
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        self.data.try_push(value)?;
+        self.sift_up(self.data.len() - 1);
+        Ok(())
+    }
+
+    /// Removes and returns the element that comes first in heap order
+    /// (greatest for `Max`, least for `Min`), or `None` if empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.data.pop()?;
+        if self.data.is_empty() {
+            return Some(last);
+        }
+        let top = core::mem::replace(&mut self.data[0], last);
+        self.sift_down(0, self.data.len());
+        Some(top)
+    }
+
+    /// Returns a mutable handle to the top element. If it's changed
+    /// through the handle, the heap re-sifts when the handle drops.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, N, K>> {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(PeekMut {
+            heap: self,
+            sift: false,
+        })
+    }
+
+    /// Consumes the heap into an ArrayVec sorted so that the element
+    /// `pop` would return first comes *last*: ascending for `Max`,
+    /// descending for `Min`. In place, no extra storage.
+    pub fn into_sorted_vec(mut self) -> ArrayVec<T, N> {
+        let mut end = self.data.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+
+    // Moves `pos` up until its parent comes before it. Only swaps, so
+    // every element stays valid if `cmp` panics.
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if !Self::before(&self.data[pos], &self.data[parent]) {
+                break;
+            }
+            self.data.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    // Moves `pos` down (within `..end`) until no child comes before it.
+    fn sift_down(&mut self, mut pos: usize, end: usize) {
+        loop {
+            let left = 2 * pos + 1;
+            if left >= end {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < end && Self::before(&self.data[right], &self.data[left]) {
+                right
+            } else {
+                left
+            };
+            if !Self::before(&self.data[child], &self.data[pos]) {
+                break;
+            }
+            self.data.swap(pos, child);
+            pos = child;
+        }
+    }
+}
+
+// Mutable handle to the top of a BinaryHeap, returned by `peek_mut`.
+pub struct PeekMut<'a, T, const N: usize, K: HeapKind = Max>
+where
+    T: Ord,
+{
+    heap: &'a mut BinaryHeap<T, N, K>,
+    // Set once the top has been handed out mutably.
+    sift: bool,
+}
+
+impl<T, const N: usize, K: HeapKind> PeekMut<'_, T, N, K>
+where
+    T: Ord,
+{
+    /// Removes the peeked element from the heap and returns it.
+    pub fn pop(mut this: Self) -> T {
+        // `pop` restores the heap itself; skip the re-sift on drop.
+        this.sift = false;
+        // Can't fail: `peek_mut` only hands out a PeekMut when non-empty.
+        this.heap.pop().unwrap()
+    }
+}
+
+impl<T, const N: usize, K: HeapKind> Deref for PeekMut<'_, T, N, K>
+where
+    T: Ord,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<T, const N: usize, K: HeapKind> DerefMut for PeekMut<'_, T, N, K>
+where
+    T: Ord,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        self.sift = true;
+        &mut self.heap.data[0]
+    }
+}
+
+// The top may now be out of place; push it back down.
+impl<T, const N: usize, K: HeapKind> Drop for PeekMut<'_, T, N, K>
+where
+    T: Ord,
+{
+    fn drop(&mut self) {
+        if self.sift {
+            let len = self.heap.data.len();
+            self.heap.sift_down(0, len);
+        }
+    }
+}
+
+impl<T, const N: usize, K: HeapKind> Default for BinaryHeap<T, N, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, K: HeapKind> Clone for BinaryHeap<T, N, K>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        BinaryHeap {
+            data: self.data.clone(),
+            _kind: core::marker::PhantomData,
+        }
+    }
+}
+
+// Prints in heap order, like `as_slice`.
+impl<T, const N: usize, K: HeapKind> fmt::Debug for BinaryHeap<T, N, K>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<'a, T, const N: usize, K: HeapKind> IntoIterator for &'a BinaryHeap<T, N, K> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
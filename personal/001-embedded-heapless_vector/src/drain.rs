@@ -0,0 +1,167 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ptr;
+
+use crate::{ArrayVec, LenType};
+
+// Draining iterator for ArrayVec, returned by `ArrayVec::drain`.
+// Moves out the elements of a range, then closes the gap on drop by
+// shifting the tail (everything after the range) back down.
+//
+// While the Drain is alive, the ArrayVec's `len` is set to the range
+// start, so leaking the Drain (e.g. `mem::forget`) only leaks the
+// drained range and the tail; it never exposes moved-out slots.
+pub struct Drain<'a, T, const N: usize, L: LenType = usize> {
+    vec: &'a mut ArrayVec<T, N, L>,
+    // Front and back cursors over the range still to be yielded.
+    index: usize,
+    end: usize,
+    // The init elements after the drained range.
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<T, const N: usize, L: LenType> ArrayVec<T, N, L> {
+    /// Removes the elements in `range` and returns them through a
+    /// double-ended `Drain` iterator. Elements not consumed by the
+    /// iterator are dropped when the Drain is dropped.
+    /// Panics if the range start is after its end, or the end is
+    /// past `len`.
+    /// If an element's `Drop` panics while the Drain drops the rest,
+    /// the others are still dropped and the tail still moves back.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, N, L>
+    where
+        R: core::ops::RangeBounds<usize>,
+    {
+        use core::ops::Bound;
+
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end,
+            "drain start (is {start}) should be <= end (is {end})"
+        );
+        assert!(
+            end <= len,
+            "drain end (is {end}) should be <= len (is {len})"
+        );
+
+        // SAFETY: Shrink `len` up front; slots `[start, len)` are now
+        // owned by the Drain until it puts the tail back.
+        self.store_len(start);
+        Drain {
+            vec: self,
+            index: start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+        }
+    }
+}
+
+impl<T, const N: usize, L: LenType> Drain<'_, T, N, L> {
+    /// Returns the elements not yet yielded as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: `[index, end)` are init and not yet moved out.
+        unsafe {
+            core::slice::from_raw_parts(
+                self.vec.values.as_ptr().add(self.index) as *const T,
+                self.end - self.index,
+            )
+        }
+    }
+}
+
+impl<T, const N: usize, L: LenType> fmt::Debug for Drain<'_, T, N, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize, L: LenType> Iterator for Drain<'_, T, N, L> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+        let i = self.index;
+        self.index += 1;
+        // SAFETY: i is in `[index, end)`, so slot is init and is read once.
+        Some(unsafe { self.vec.values[i].assume_init_read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, const N: usize, L: LenType> DoubleEndedIterator for Drain<'_, T, N, L> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: Same as `next`, from the other end.
+        Some(unsafe { self.vec.values[self.end].assume_init_read() })
+    }
+}
+
+impl<T, const N: usize, L: LenType> ExactSizeIterator for Drain<'_, T, N, L> {}
+
+impl<T, const N: usize, L: LenType> FusedIterator for Drain<'_, T, N, L> {}
+
+impl<T, const N: usize, L: LenType> Drop for Drain<'_, T, N, L> {
+    fn drop(&mut self) {
+        // Moves the tail back and restores `len`. Runs from a guard so
+        // it still happens if dropping a remaining element panics.
+        struct TailGuard<'r, 'a, T, const N: usize, L: LenType>(&'r mut Drain<'a, T, N, L>);
+
+        impl<T, const N: usize, L: LenType> Drop for TailGuard<'_, '_, T, N, L> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let start = drain.vec.len();
+                if drain.tail_len > 0 && drain.tail_start != start {
+                    // SAFETY: Tail slots are init; destination slots were
+                    // drained (moved out or dropped). `ptr::copy` handles
+                    // overlap.
+                    unsafe {
+                        let base = drain.vec.values.as_mut_ptr();
+                        ptr::copy(base.add(drain.tail_start), base.add(start), drain.tail_len);
+                    }
+                }
+                drain.vec.store_len(start + drain.tail_len);
+            }
+        }
+
+        let guard = TailGuard(self);
+        let drain = &mut *guard.0;
+
+        // Drop the elements the caller didn't consume. Mark them as
+        // taken first so the guard never sees them again.
+        let remaining = drain.end - drain.index;
+        // SAFETY: `[index, end)` are init and owned by the Drain.
+        unsafe {
+            let to_drop = ptr::slice_from_raw_parts_mut(
+                drain.vec.values.as_mut_ptr().add(drain.index) as *mut T,
+                remaining,
+            );
+            drain.index = drain.end;
+            ptr::drop_in_place(to_drop);
+        }
+        // `guard` drops here and moves the tail back.
+    }
+}
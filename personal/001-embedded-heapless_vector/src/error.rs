@@ -0,0 +1,35 @@
+use core::fmt;
+
+// Error returned when an operation needs more room than is left.
+// Carries the rejected element back to the caller, like the old
+// `Err(value)` did, so nothing is lost on a full buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    /// Wraps the element that didn't fit.
+    pub const fn new(element: T) -> Self {
+        CapacityError { element }
+    }
+
+    /// Takes back the element that didn't fit.
+    pub fn element(self) -> T {
+        self.element
+    }
+
+    /// Drops the element, leaving a unit error (`CapacityError<()>`)
+    /// that is `'static` and cheap to pass up through `?`.
+    pub fn simplify(self) -> CapacityError {
+        CapacityError { element: () }
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl<T> core::error::Error for CapacityError<T> where T: fmt::Debug {}
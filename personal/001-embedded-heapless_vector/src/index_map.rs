@@ -0,0 +1,537 @@
+// Fixed-capacity hash map and set using open addressing. Capacity must
+// be a power of two. Iteration follows insertion order until something
+// is removed: removal moves the last entry into the gap (like
+// `swap_remove`), so that entry changes position.
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+
+use crate::map::MapStorage;
+use crate::{ArrayVec, ArrayVecIntoIter, CapacityError, Entry, MapIter, MapIterMut};
+
+// 64-bit FNV-1a: tiny, no state beyond a `u64`, no allocation. Good
+// enough for the short integer/string keys of on-device tables; not
+// DoS-resistant, so pass your own `S` for untrusted keys.
+#[derive(Clone, Copy)]
+pub struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    // Fold the high bits in; the table only looks at the low ones.
+    fn finish(&self) -> u64 {
+        self.0 ^ (self.0 >> 32)
+    }
+}
+
+pub type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
+
+// Slot in the index table: where the entry lives in `entries`, plus its
+// hash so probing and backward shifts don't rehash keys. This is the
+// only copy of the hash.
+#[derive(Clone, Copy)]
+struct Pos {
+    index: usize,
+    hash: u32,
+}
+
+// Hash map with linear probing over a power-of-two table of `N` slots.
+// Entries live densely in an ArrayVec in insertion order; the table
+// only stores their positions. Removal swaps the last entry into the
+// gap (like `swap_remove`) and backward-shifts the probe chain, so
+// there are no tombstones.
+pub struct IndexMap<K, V, const N: usize, S = FnvBuildHasher> {
+    entries: ArrayVec<(K, V), N>,
+    table: [Option<Pos>; N],
+    hash_builder: S,
+}
+
+impl<K, V, const N: usize, S> IndexMap<K, V, N, S> {
+    const POWER_OF_TWO: () = assert!(
+        N.is_power_of_two(),
+        "IndexMap capacity N must be a power of two"
+    );
+
+    /// Creates a new empty map with the given hasher builder.
+    pub const fn with_hasher(hash_builder: S) -> Self {
+        let () = Self::POWER_OF_TWO;
+        IndexMap {
+            entries: ArrayVec::new(),
+            table: [None; N],
+            hash_builder,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if no more entries fit.
+    pub const fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.table = [None; N];
+    }
+
+    /// Iterates over `(&key, &value)` in insertion order (as changed by
+    /// any `remove`s).
+    pub fn iter(&self) -> MapIter<'_, K, V> {
+        MapIter::new(&self.entries)
+    }
+
+    /// Iterates over `(&key, &mut value)` in insertion order.
+    pub fn iter_mut(&mut self) -> MapIterMut<'_, K, V> {
+        MapIterMut::new(&mut self.entries)
+    }
+
+    /// Iterates over the keys in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Iterates over the values in insertion order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    /// Iterates mutably over the values in insertion order.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.entries.iter_mut().map(|(_, v)| v)
+    }
+
+    // Ideal table slot for a hash.
+    fn home(hash: u32) -> usize {
+        hash as usize & (N - 1)
+    }
+
+    // Next table slot, wrapping.
+    fn next(slot: usize) -> usize {
+        (slot + 1) & (N - 1)
+    }
+
+    // Table slot pointing at entry `index`, whose key hashes to `hash`.
+    // It's always there.
+    fn slot_for(&self, hash: u32, index: usize) -> usize {
+        let mut slot = Self::home(hash);
+        while !matches!(self.table[slot], Some(pos) if pos.index == index) {
+            slot = Self::next(slot);
+        }
+        slot
+    }
+
+    // Clears table slot `hole`, then pulls later entries of the probe
+    // chain back so every entry stays reachable from its home slot.
+    fn backward_shift(&mut self, mut hole: usize) {
+        self.table[hole] = None;
+        let mut slot = Self::next(hole);
+        // At most `N - 1` other slots; stop early at the first empty one.
+        for _ in 1..N {
+            let Some(pos) = self.table[slot] else {
+                break;
+            };
+            // Move it if its home is not cyclically within (hole, slot].
+            let home = Self::home(pos.hash);
+            if slot.wrapping_sub(home) & (N - 1) >= slot.wrapping_sub(hole) & (N - 1) {
+                self.table[hole] = Some(pos);
+                self.table[slot] = None;
+                hole = slot;
+            }
+            slot = Self::next(slot);
+        }
+    }
+}
+
+impl<K, V, const N: usize, S> IndexMap<K, V, N, S>
+where
+    S: Default,
+{
+    /// Creates a new empty IndexMap. Fails to compile unless `N` is a
+    /// power of two.
+    pub fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, const N: usize, S> IndexMap<K, V, N, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    // Only the low 32 bits are kept; that's plenty to index the table
+    // and to skip most key comparisons on collisions.
+    fn hash<Q>(&self, key: &Q) -> u32
+    where
+        Q: Hash + ?Sized,
+    {
+        self.hash_builder.hash_one(key) as u32
+    }
+
+    // Table slot pointing at entry `index`. The hash isn't stored per
+    // entry (only in the table), so the key is hashed again.
+    fn slot_of(&self, index: usize) -> usize {
+        self.slot_for(self.hash(&self.entries[index].0), index)
+    }
+
+    // Removes entry `index` (already unlinked from the table), moving
+    // the last entry into its place and repointing that one's slot.
+    fn swap_remove_index(&mut self, index: usize) -> (K, V) {
+        let last = self.entries.len() - 1;
+        if index != last {
+            let slot = self.slot_of(last);
+            if let Some(pos) = &mut self.table[slot] {
+                pos.index = index;
+            }
+        }
+        self.entries.swap_remove(index)
+    }
+
+    // Table slot and entry index for `key`, if present.
+    fn find<Q>(&self, hash: u32, key: &Q) -> Option<(usize, usize)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mut slot = Self::home(hash);
+        for _ in 0..N {
+            let pos = self.table[slot]?;
+            if pos.hash == hash && self.entries[pos.index].0.borrow() == key {
+                return Some((slot, pos.index));
+            }
+            slot = Self::next(slot);
+        }
+        None
+    }
+
+    /// Returns a reference to the value for `key`, if present.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (_, index) = self.find(self.hash(key), key)?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns a mutable reference to the value for `key`, if present.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (_, index) = self.find(self.hash(key), key)?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Returns `true` if `key` is present.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.find(self.hash(key), key).is_some()
+    }
+
+    /// Inserts a pair. If `key` was present, its value is replaced (the
+    /// entry keeps its position) and the old value is returned. If the
+    /// map is full, the pair is handed back in a `CapacityError`.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+        match self.entry(key) {
+            Entry::Occupied(mut e) => Ok(Some(e.insert(value))),
+            Entry::Vacant(e) => e.try_insert(value).map(|_| None),
+        }
+    }
+
+    /// Removes `key` and returns its value, if present. The last entry
+    /// moves into the removed one's place in iteration order.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes `key` and returns the stored pair, if present.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let (_, index) = self.find(self.hash(key), key)?;
+        Some(self.remove_at(index))
+    }
+
+    /// Gets the entry for `key` for in-place insert-or-update.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, Self> {
+        let hash = self.hash(&key);
+        match self.find(hash, &key) {
+            Some((_, index)) => Entry::occupied(self, index),
+            None => Entry::vacant(self, key, hash),
+        }
+    }
+}
+
+// A vacant entry carries the key's hash over from the failed lookup.
+impl<K, V, const N: usize, S> MapStorage<K, V> for IndexMap<K, V, N, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    type Hint = u32;
+
+    fn pairs(&self) -> &[(K, V)] {
+        &self.entries
+    }
+
+    fn pairs_mut(&mut self) -> &mut [(K, V)] {
+        &mut self.entries
+    }
+
+    fn remove_at(&mut self, index: usize) -> (K, V) {
+        let slot = self.slot_of(index);
+        self.backward_shift(slot);
+        self.swap_remove_index(index)
+    }
+
+    fn insert_new(&mut self, key: K, value: V, hash: u32) -> Result<usize, CapacityError<(K, V)>> {
+        if self.is_full() {
+            return Err(CapacityError::new((key, value)));
+        }
+        // Not full, so the table has an empty slot on the probe path.
+        let mut slot = Self::home(hash);
+        while self.table[slot].is_some() {
+            slot = Self::next(slot);
+        }
+        let index = self.entries.len();
+        self.table[slot] = Some(Pos { index, hash });
+        // Can't fail: checked above.
+        let _ = self.entries.try_push((key, value));
+        Ok(index)
+    }
+}
+
+impl<K, V, const N: usize, S> Default for IndexMap<K, V, N, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize, S> Clone for IndexMap<K, V, N, S>
+where
+    K: Clone,
+    V: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        IndexMap {
+            entries: self.entries.clone(),
+            table: self.table,
+            hash_builder: self.hash_builder.clone(),
+        }
+    }
+}
+
+impl<K, V, const N: usize, S> fmt::Debug for IndexMap<K, V, N, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Equal if they hold the same pairs, in any order.
+impl<K, V, const N: usize, const M: usize, S, S2> PartialEq<IndexMap<K, V, M, S2>>
+    for IndexMap<K, V, N, S>
+where
+    K: Eq + Hash,
+    V: PartialEq,
+    S: BuildHasher,
+    S2: BuildHasher,
+{
+    fn eq(&self, other: &IndexMap<K, V, M, S2>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V, const N: usize, S> Eq for IndexMap<K, V, N, S>
+where
+    K: Eq + Hash,
+    V: Eq,
+    S: BuildHasher,
+{
+}
+
+impl<'a, K, V, const N: usize, S> IntoIterator for &'a IndexMap<K, V, N, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize, S> IntoIterator for &'a mut IndexMap<K, V, N, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = MapIterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// Owned pairs come straight out of the backing ArrayVec.
+impl<K, V, const N: usize, S> IntoIterator for IndexMap<K, V, N, S> {
+    type Item = (K, V);
+    type IntoIter = ArrayVecIntoIter<(K, V), N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+// Set counterpart of IndexMap: distinct values in insertion order.
+pub struct IndexSet<T, const N: usize, S = FnvBuildHasher> {
+    map: IndexMap<T, (), N, S>,
+}
+
+impl<T, const N: usize, S> IndexSet<T, N, S> {
+    /// Creates a new empty set with the given hasher builder.
+    pub const fn with_hasher(hash_builder: S) -> Self {
+        IndexSet {
+            map: IndexMap::with_hasher(hash_builder),
+        }
+    }
+
+    /// Returns the number of values.
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Removes all values.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Iterates in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.map.keys()
+    }
+}
+
+impl<T, const N: usize, S> IndexSet<T, N, S>
+where
+    S: Default,
+{
+    /// Creates a new empty IndexSet. Fails to compile unless `N` is a
+    /// power of two.
+    pub fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T, const N: usize, S> IndexSet<T, N, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Adds a value. Returns `Ok(true)` if it was new, `Ok(false)` if
+    /// already present, or hands it back if the set is full.
+    pub fn insert(&mut self, value: T) -> Result<bool, CapacityError<T>> {
+        match self.map.entry(value) {
+            Entry::Occupied(_) => Ok(false),
+            Entry::Vacant(e) => e
+                .try_insert(())
+                .map(|_| true)
+                .map_err(|err| CapacityError::new(err.element().0)),
+        }
+    }
+
+    /// Returns `true` if `value` is in the set.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Removes `value`, returning whether it was present.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+}
+
+impl<T, const N: usize, S> Default for IndexSet<T, N, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, S> Clone for IndexSet<T, N, S>
+where
+    T: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        IndexSet {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T, const N: usize, S> fmt::Debug for IndexSet<T, N, S>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
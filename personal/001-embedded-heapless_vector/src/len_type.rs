@@ -0,0 +1,77 @@
+use core::fmt;
+use core::mem::{MaybeUninit, size_of};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+    impl Sealed for usize {}
+}
+
+// Integer type an ArrayVec stores its length in. Picking a smaller one
+// shrinks the struct, e.g. `ArrayVec<u8, 16, u8>` is 17 bytes instead of
+// 24 on a 64-bit host (or 20 on a 32-bit MCU).
+//
+// Sealed: only `u8`, `u16`, `u32` and `usize` implement it. The const fn
+// conversions below rely on that.
+pub trait LenType: Copy + Ord + fmt::Debug + sealed::Sealed + 'static {
+    /// Largest length (and so capacity `N`) this type can hold.
+    const MAX: usize;
+}
+
+impl LenType for u8 {
+    const MAX: usize = u8::MAX as usize;
+}
+
+impl LenType for u16 {
+    const MAX: usize = u16::MAX as usize;
+}
+
+impl LenType for u32 {
+    // Saturate on 16-bit targets, where `u32::MAX` doesn't fit a usize.
+    const MAX: usize = if size_of::<u32>() > size_of::<usize>() {
+        usize::MAX
+    } else {
+        u32::MAX as usize
+    };
+}
+
+impl LenType for usize {
+    const MAX: usize = usize::MAX;
+}
+
+// Trait methods can't be called from a const fn (yet), so the
+// conversions dispatch on the size of `L` instead. Sound because the
+// trait is sealed: every `L` is an unsigned integer of that size.
+pub(crate) const fn to_usize<L: LenType>(len: L) -> usize {
+    let p = &len as *const L;
+    // SAFETY: `L` is one of u8/u16/u32/usize; read it as the unsigned
+    // integer of the same size.
+    unsafe {
+        match size_of::<L>() {
+            1 => *(p as *const u8) as usize,
+            2 => *(p as *const u16) as usize,
+            4 => *(p as *const u32) as usize,
+            _ => *(p as *const usize),
+        }
+    }
+}
+
+// Callers keep `len <= N`, and ArrayVec checks `N <= L::MAX` at compile
+// time, so the narrowing `as` casts never truncate.
+pub(crate) const fn from_usize<L: LenType>(len: usize) -> L {
+    let mut out = MaybeUninit::<L>::uninit();
+    let p = out.as_mut_ptr();
+    // SAFETY: Same as `to_usize`; every byte of `out` gets written.
+    unsafe {
+        match size_of::<L>() {
+            1 => *(p as *mut u8) = len as u8,
+            2 => *(p as *mut u16) = len as u16,
+            4 => *(p as *mut u32) = len as u32,
+            _ => *(p as *mut usize) = len,
+        }
+        out.assume_init()
+    }
+}
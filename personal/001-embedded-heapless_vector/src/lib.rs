@@ -1,15 +1,92 @@
+// Heap-free by construction: no `std`, no `alloc`. The `alloc` feature
+// adds SmallVec and Vec conversions, and `std` adds host-side trait
+// impls on top (see `alloc_impls`, `std_impls`).
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::fmt;
 use core::mem::{ManuallyDrop, MaybeUninit};
 use core::ptr;
 
-#[derive(Debug)]
-pub struct ArrayVec<T, const N: usize> {
+mod aligned;
+#[cfg(feature = "alloc")]
+mod alloc_impls;
+mod array_deque;
+mod array_string;
+mod binary_heap;
+mod drain;
+mod error;
+mod index_map;
+mod len_type;
+mod linear_map;
+mod lru_cache;
+mod map;
+mod slab;
+mod slice_traits;
+#[cfg(feature = "alloc")]
+mod small_vec;
+mod sorted_array_vec;
+mod spsc;
+#[cfg(feature = "std")]
+mod std_impls;
+
+pub use aligned::{Align8, Align16, Align32, Align64, Align128, AlignedArrayVec, Alignment};
+pub use array_deque::{ArrayDeque, ArrayDequeIntoIter, ArrayDequeIter, ArrayDequeIterMut};
+pub use array_string::ArrayString;
+pub use binary_heap::{BinaryHeap, HeapKind, Max, Min, PeekMut};
+pub use drain::Drain;
+pub use error::CapacityError;
+pub use index_map::{FnvBuildHasher, FnvHasher, IndexMap, IndexSet};
+pub use len_type::LenType;
+pub use linear_map::{LinearMap, LinearSet};
+pub use lru_cache::{LruCache, LruIter};
+pub use map::{Entry, MapIter, MapIterMut, OccupiedEntry, VacantEntry};
+pub use slab::{Handle, Slab, SlabIter, SlabIterMut};
+#[cfg(feature = "alloc")]
+pub use small_vec::{SmallVec, SmallVecDrain, SmallVecIntoIter};
+pub use sorted_array_vec::{Ascending, Comparator, Descending, SortedArrayVec};
+pub use spsc::{Consumer, Producer, SpscQueue};
+
+// `L` is the integer type `len` is stored in (see `LenType`). Defaults
+// to `usize`; `ArrayVec<T, N, u8>` etc. save space on small buffers.
+// `repr(C)` with `values` first so the storage sits at offset 0, which
+// AlignedArrayVec relies on.
+//
+// Panic safety: every operation that runs user code (`Drop`, `Clone`,
+// closures, iterators) keeps `len` covering exactly the init slots at
+// each point user code can panic, or fixes it up from a drop guard. So a
+// panic never double-drops or exposes an uninit slot, and the ArrayVec
+// is left valid (with the contents noted on each method). Elements are
+// only leaked where std's Vec would leak them too (a `Drop` panicking
+// while already unwinding aborts).
+//
+// Zero-sized `T` and `N == 0` need no special cases and behave exactly
+// like any other ArrayVec: capacity is still `N` (so `ArrayVec<Marker,
+// N>` is a counter bounded by `N`, taking only the size of `L`), every
+// push counts, every element is yielded by iterators and dropped once.
+// With `N == 0` every push fails and every iterator is empty. The raw
+// pointer code below is fine for both: pointers into a zero-sized array
+// are dangling but aligned, which is all `from_raw_parts`, `add`, `copy`
+// and `read` need when no bytes are touched.
+#[repr(C)]
+pub struct ArrayVec<T, const N: usize, L: LenType = usize> {
     values: [MaybeUninit<T>; N],
-    len: usize,
+    len: L,
 }
 
-impl<T, const N: usize> ArrayVec<T, N> {
-    /// Creates a new empty ArrayVec
-    pub fn new() -> Self {
+impl<T, const N: usize, L: LenType> ArrayVec<T, N, L> {
+    // Evaluated once per (N, L); referencing it from a constructor turns
+    // "N doesn't fit in L" into a compile-time error.
+    const LEN_FITS: () = assert!(N <= L::MAX, "capacity N does not fit in length type L");
+
+    /// Creates a new empty ArrayVec. `const`, so it works in `static`s.
+    /// Fails to compile if `N` is too big for the length type `L`.
+    pub const fn new() -> Self {
+        let () = Self::LEN_FITS;
         // [MaybeUninit<T>; N] is zero-initialized to uninit by default.
         // Meaning the array starts from a blank slate waiting to be
         // initialized by `write()`; filling uninit elements with
@@ -18,27 +95,272 @@ impl<T, const N: usize> ArrayVec<T, N> {
             // values: unsafe { MaybeUninit::uninit().assume_init() },
             // Same as the commented code above but safer.
             values: [const { MaybeUninit::uninit() }; N],
-            len: 0,
+            len: len_type::from_usize(0),
         }
     }
 
-    /// Pushes a value if there's space, returning `Err(value)` if full.
+    // Stores `len` as `L`. Callers keep `len <= N`, and `LEN_FITS`
+    // guarantees `N` fits in `L`.
+    const fn store_len(&mut self, len: usize) {
+        self.len = len_type::from_usize(len);
+    }
+
+    /// Pushes a value if there's space, returning a `CapacityError`
+    /// holding `value` if full.
     /// Safe: `.write()` takes ownership and marks the slot as
-    /// initialized.
-    pub fn try_push(&mut self, value: T) -> Result<(), T> {
-        if self.len == N {
-            return Err(value);
+    /// initialized. `const`, so tables can be filled at compile time.
+    pub const fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        let len = self.len();
+        if len == N {
+            return Err(CapacityError::new(value));
         }
-        self.values[self.len].write(value);
-        self.len += 1;
+        self.values[len].write(value);
+        self.store_len(len + 1);
         Ok(())
     }
 
+    /// Inserts a value at `index`, shifting all elements after it one
+    /// slot to the right. Hands `value` back in a `CapacityError` if
+    /// full, like `try_push`.
+    /// Panics if `index > len`.
+    /// SAFETY: Unsafe internally: `ptr::copy` handles the overlapping
+    /// shift, and `len` is only bumped once the slot is written.
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), CapacityError<T>> {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+        if len == N {
+            return Err(CapacityError::new(value));
+        }
+        unsafe {
+            let p = self.values.as_mut_ptr().add(index);
+            // Shift `[index, len)` up by one; slot `len` is uninit so
+            // nothing gets overwritten.
+            ptr::copy(p, p.add(1), len - index);
+            (*p).write(value);
+        }
+        self.store_len(len + 1);
+        Ok(())
+    }
+
+    /// Removes and returns the element at `index`, shifting all elements
+    /// after it one slot to the left. Panics if `index >= len`.
+    /// SAFETY: Unsafe internally: the value is read out before its slot
+    /// is overwritten by the shift, so it is moved exactly once.
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "removal index (is {index}) should be < len (is {len})"
+        );
+        unsafe {
+            let p = self.values.as_mut_ptr().add(index);
+            let value = (*p).assume_init_read();
+            ptr::copy(p.add(1), p, len - index - 1);
+            self.store_len(len - 1);
+            value
+        }
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the
+    /// last element. O(1), but does not preserve ordering.
+    /// Panics if `index >= len`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "swap_remove index (is {index}) should be < len (is {len})"
+        );
+        unsafe {
+            let value = self.values[index].assume_init_read();
+            // Move the last element into the hole (a no-op copy when
+            // `index` is the last slot).
+            let last = self.values.as_ptr().add(len - 1);
+            ptr::copy(last, self.values.as_mut_ptr().add(index), 1);
+            self.store_len(len - 1);
+            value
+        }
+    }
+
+    /// Shortens the ArrayVec to `len` elements, dropping the rest.
+    /// Does nothing if `len` is greater than the current length.
+    /// SAFETY: `len` is lowered *before* the tail is dropped, so a
+    /// panicking `Drop` can't cause those slots to be dropped twice.
+    /// The rest of the tail is still dropped after such a panic (slice
+    /// drop glue keeps going), so nothing leaks.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        let tail_len = self.len() - len;
+        self.store_len(len);
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(
+                self.values.as_mut_ptr().add(len) as *mut T,
+                tail_len,
+            );
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Drops all elements, leaving the ArrayVec empty. Same panic
+    /// behaviour as `truncate(0)`: empty afterwards, nothing leaked.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, dropping the
+    /// rest in place. Preserves the order of kept elements.
+    /// If `f` panics, see `retain_mut`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|value| f(value));
+    }
+
+    /// Like `retain`, but `f` gets a mutable reference to each element.
+    /// SAFETY: Unsafe internally: kept elements are compacted towards the
+    /// front as we go. A guard closes the gap and fixes `len` even if `f`
+    /// or a `Drop` panics, so no slot is dropped twice or left dangling.
+    /// After such a panic the ArrayVec holds the elements kept so far
+    /// plus every unvisited one (including the one `f` panicked on).
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        struct Guard<'a, T, const N: usize, L: LenType> {
+            vec: &'a mut ArrayVec<T, N, L>,
+            processed: usize,
+            deleted: usize,
+            original_len: usize,
+        }
+
+        impl<T, const N: usize, L: LenType> Drop for Guard<'_, T, N, L> {
+            fn drop(&mut self) {
+                // Shift unprocessed elements (only left over after a
+                // panic) down over the deleted ones.
+                if self.deleted > 0 {
+                    unsafe {
+                        let base = self.vec.values.as_mut_ptr();
+                        ptr::copy(
+                            base.add(self.processed),
+                            base.add(self.processed - self.deleted),
+                            self.original_len - self.processed,
+                        );
+                    }
+                }
+                self.vec.store_len(self.original_len - self.deleted);
+            }
+        }
+
+        let original_len = self.len();
+        let mut g = Guard {
+            vec: self,
+            processed: 0,
+            deleted: 0,
+            original_len,
+        };
+
+        while g.processed < original_len {
+            // SAFETY: `processed < original_len`, so slot is init.
+            let cur = unsafe { &mut *(g.vec.values.as_mut_ptr().add(g.processed) as *mut T) };
+            if !f(cur) {
+                // Count it before dropping so a panicking `Drop` doesn't
+                // leave it for the guard to shift back in.
+                g.processed += 1;
+                g.deleted += 1;
+                unsafe { ptr::drop_in_place(cur) };
+                continue;
+            }
+            if g.deleted > 0 {
+                // SAFETY: The hole at `processed - deleted` was dropped.
+                unsafe {
+                    let hole = g.vec.values.as_mut_ptr().add(g.processed - g.deleted);
+                    ptr::copy_nonoverlapping(cur as *const T, hole as *mut T, 1);
+                }
+            }
+            g.processed += 1;
+        }
+        // `g` drops here and commits the new `len`.
+    }
+
+    /// Removes consecutive elements that resolve to the same key.
+    /// Panic behaviour as for `dedup_by`.
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes consecutive elements for which `same_bucket(current,
+    /// previous)` returns `true`, keeping the first of each run.
+    /// SAFETY: Unsafe internally: same compaction scheme (and panic
+    /// guard) as `retain_mut`. If `same_bucket` or a `Drop` panics, the
+    /// elements kept so far and all unread ones stay, in order.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        struct Guard<'a, T, const N: usize, L: LenType> {
+            vec: &'a mut ArrayVec<T, N, L>,
+            read: usize,
+            write: usize,
+            original_len: usize,
+        }
+
+        impl<T, const N: usize, L: LenType> Drop for Guard<'_, T, N, L> {
+            fn drop(&mut self) {
+                // Shift the unread elements (only left over after a
+                // panic) down to the write cursor.
+                let unread = self.original_len - self.read;
+                unsafe {
+                    let base = self.vec.values.as_mut_ptr();
+                    ptr::copy(base.add(self.read), base.add(self.write), unread);
+                }
+                self.vec.store_len(self.write + unread);
+            }
+        }
+
+        let original_len = self.len();
+        if original_len <= 1 {
+            return;
+        }
+        // Slot 0 is always kept.
+        let mut g = Guard {
+            vec: self,
+            read: 1,
+            write: 1,
+            original_len,
+        };
+
+        while g.read < original_len {
+            // SAFETY: `read` is init and `write - 1 < read` is the last
+            // kept element; they never alias.
+            let base = g.vec.values.as_mut_ptr() as *mut T;
+            let (cur, prev) = unsafe { (&mut *base.add(g.read), &mut *base.add(g.write - 1)) };
+            if same_bucket(cur, prev) {
+                g.read += 1;
+                unsafe { ptr::drop_in_place(cur) };
+                continue;
+            }
+            if g.read != g.write {
+                unsafe { ptr::copy_nonoverlapping(cur as *const T, base.add(g.write), 1) };
+            }
+            g.write += 1;
+            g.read += 1;
+        }
+    }
+
     /// Returns a reference to the element at `index` if within bounds
     /// and initialized.
     /// SAFETY: Unsafe internally: Assumes first `len` slots are init.
-    pub fn get(&self, index: usize) -> Option<&T> {
-        if index >= self.len {
+    pub const fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
             return None;
         }
         // Same as the commented code below
@@ -49,30 +371,86 @@ impl<T, const N: usize> ArrayVec<T, N> {
     /// Pops the last value if any, returning it (or `None` if empty).
     /// SAFETY: Safe: Uses `.assume_init_read()` to extract and mark
     /// as uninit.
-    pub fn pop(&mut self) -> Option<T> {
-        if self.len == 0 {
+    pub const fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
             return None;
         }
-        self.len -= 1;
-        Some(unsafe { self.values[self.len].assume_init_read() })
+        self.store_len(len - 1);
+        Some(unsafe { self.values[len - 1].assume_init_read() })
     }
 
     /// Returns the current length.
-    pub fn len(&self) -> usize {
-        self.len
+    pub const fn len(&self) -> usize {
+        len_type::to_usize(self.len)
+    }
+
+    /// Returns `true` if there are no init elements.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns how many more elements fit (`N - len`).
+    pub const fn remaining_capacity(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Returns `true` if no more elements fit.
+    pub const fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns a raw pointer to the start of the storage. The first `len`
+    /// elements are init; the rest of the `N` slots may not be.
+    pub const fn as_ptr(&self) -> *const T {
+        self.values.as_ptr() as *const T
+    }
+
+    /// Returns a raw mutable pointer to the start of the storage. Same
+    /// rules as `as_ptr`.
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.values.as_mut_ptr() as *mut T
+    }
+
+    /// Returns the uninit tail (slots `len..N`) for writing in place,
+    /// e.g. as a DMA receive buffer. Writes only become part of the
+    /// ArrayVec once committed with `set_len`.
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let len = self.len();
+        &mut self.values[len..]
+    }
+
+    /// Sets the length without dropping or initializing anything.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee `new_len <= N` and that the first
+    /// `new_len` slots are init. Shrinking this way leaks (does not
+    /// drop) the elements cut off.
+    pub const unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= N);
+        self.store_len(new_len);
     }
 
     /// Instead of `into_arr` lets return a slice using
     /// `slice::from_raw_parts()`.
     /// Returns a slice over init elements (& first `len` slots).
     /// SAFETY: Unsafe internally, but safe API: assumes invariant holds.
-    pub fn as_slice(&self) -> &[T] {
-        unsafe { core::slice::from_raw_parts(self.values.as_ptr() as *const T, self.len) }
+    /// For ZSTs or `N == 0` the pointer is dangling but aligned, which
+    /// `from_raw_parts` allows.
+    pub const fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.values.as_ptr() as *const T, self.len()) }
     }
 
     /// Returns a mutable slice over init elements.
-    pub fn as_mut_slice(&mut self) -> &mut [T] {
-        unsafe { core::slice::from_raw_parts_mut(self.values.as_mut_ptr() as *mut T, self.len) }
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        unsafe { core::slice::from_raw_parts_mut(self.values.as_mut_ptr() as *mut T, len) }
     }
 
     /// Use slice iterator for immutable iteration
@@ -86,49 +464,206 @@ impl<T, const N: usize> ArrayVec<T, N> {
     }
 }
 
-impl<T, const N: usize> ArrayVec<T, N>
+impl<T, const N: usize, L: LenType> ArrayVec<T, N, L>
 where
     T: Copy,
 {
-    pub fn show_init<'a>(&self, other: &'a mut ArrayVec<Option<T>, N>) -> &'a [Option<T>] {
-        let mut count = 0;
+    /// Builds an ArrayVec from a full array, keeping only the first
+    /// `len` elements as init. `const`, so partially filled tables can
+    /// live in a `static` (and in flash).
+    /// `T: Copy` because the elements past `len` are discarded, and
+    /// dropping isn't allowed in const fns.
+    /// Panics (at compile time, in a const context) if `len > N`.
+    pub const fn from_array_len(array: [T; N], len: usize) -> Self {
+        let () = Self::LEN_FITS;
+        assert!(len <= N, "from_array_len: len should be <= N");
+        // SAFETY: `[T; N]` and `[MaybeUninit<T>; N]` have the same layout,
+        // and `T: Copy` means no drop is skipped.
+        let values = unsafe { ptr::read(&array as *const [T; N] as *const [MaybeUninit<T>; N]) };
+        ArrayVec {
+            values,
+            len: len_type::from_usize(len),
+        }
+    }
+}
+
+impl<T, const N: usize, L: LenType> ArrayVec<T, N, L>
+where
+    T: PartialEq,
+{
+    /// Removes consecutive repeated elements, keeping the first of each run.
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+}
+
+impl<T, const N: usize, L: LenType> Default for ArrayVec<T, N, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, L: LenType> ArrayVec<T, N, L> {
+    /// Returns a view of all `N` slots: init slots as `Some(&T)`,
+    /// uninit slots as `None`. Borrows the ArrayVec; allocates nothing.
+    pub fn show_init(&self) -> InitView<'_, T, N, L> {
+        InitView { arr_vec: self }
+    }
+}
+
+// Occupancy view returned by `ArrayVec::show_init`.
+// `{:?}` prints it like `[Some(1), Some(2), None]`.
+pub struct InitView<'a, T, const N: usize, L: LenType = usize> {
+    arr_vec: &'a ArrayVec<T, N, L>,
+}
+
+impl<'a, T, const N: usize, L: LenType> InitView<'a, T, N, L> {
+    /// Iterates over all `N` slots in order.
+    pub fn iter(&self) -> impl Iterator<Item = Option<&'a T>> + 'a {
+        let uninit = N - self.arr_vec.len();
+        self.arr_vec
+            .as_slice()
+            .iter()
+            .map(Some)
+            .chain(core::iter::repeat_n(None, uninit))
+    }
+}
+
+impl<T, const N: usize, L: LenType> fmt::Debug for InitView<'_, T, N, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Implement Drop trait to safely deallocate init elements.
+impl<T, const N: usize, L: LenType> Drop for ArrayVec<T, N, L> {
+    fn drop(&mut self) {
+        // Drops the first `len` init elements as one slice, so if one
+        // element's `Drop` panics the rest are still dropped rather
+        // than leaked.
+        self.clear();
+    }
+}
 
+// Standard traits. All of them only look at the init prefix (the first
+// `len` slots), mostly by deferring to the slice impls.
+impl<T, const N: usize, L: LenType> Clone for ArrayVec<T, N, L>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut arr_vec = Self::new();
         for item in self {
-            let _ = other.try_push(Some(*item)); // deref `T` to copy primitive value.
-            count += 1;
+            // Can't fail: same capacity. If `clone` panics, `arr_vec`
+            // drops the clones made so far and `self` is untouched.
+            let _ = arr_vec.try_push(item.clone());
         }
+        arr_vec
+    }
+}
 
-        while count < N {
-            let _ = other.try_push(None);
-            count += 1;
+// Deref, indexing, AsRef/Borrow, comparisons with slices and arrays,
+// Eq, Ord and Hash.
+slice_traits::impl_slice_traits!([const N: usize, L: LenType] ArrayVec<T, N, L>);
+
+// Equality compares init elements only, so two ArrayVecs with different
+// capacities (or garbage in their uninit slots) can still be equal.
+impl<T, U, const N: usize, const M: usize, L: LenType, L2: LenType> PartialEq<ArrayVec<U, M, L2>>
+    for ArrayVec<T, N, L>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &ArrayVec<U, M, L2>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+// Prints init elements like a list: `[1, 2, 3]`. The raw
+// `[MaybeUninit<T>; N]` says nothing useful, so it's never printed.
+// Alternate mode (`{:#?}`) also shows the capacity.
+impl<T, const N: usize, L: LenType> fmt::Debug for ArrayVec<T, N, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("ArrayVec")
+                .field("values", &self.as_slice())
+                .field("capacity", &N)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
         }
-        other.as_slice()
     }
 }
 
-// Implement Drop trait to safely deallocate init elements.
-impl<T, const N: usize> Drop for ArrayVec<T, N> {
-    fn drop(&mut self) {
-        // Explicity drop the first `len` initialized elements.
-        // SAFETY: Will not drop uninit values
-        for i in 0..self.len {
-            unsafe {
-                self.values[i].assume_init_drop();
-            }
+// A full array moves in as-is: every slot becomes init.
+impl<T, const N: usize, L: LenType> From<[T; N]> for ArrayVec<T, N, L> {
+    fn from(array: [T; N]) -> Self {
+        // SAFETY: `[T; N]` and `[MaybeUninit<T>; N]` have the same layout.
+        // ManuallyDrop stops the array from dropping what we just moved.
+        let array = ManuallyDrop::new(array);
+        let values = unsafe { ptr::read(&*array as *const [T; N] as *const [MaybeUninit<T>; N]) };
+        let () = Self::LEN_FITS;
+        ArrayVec {
+            values,
+            len: len_type::from_usize(N),
+        }
+    }
+}
+
+// Clones from a slice. The caller still owns the slice, so the error
+// doesn't carry it.
+impl<'a, T, const N: usize, L: LenType> TryFrom<&'a [T]> for ArrayVec<T, N, L>
+where
+    T: Clone,
+{
+    type Error = CapacityError;
+
+    fn try_from(slice: &'a [T]) -> Result<Self, Self::Error> {
+        if slice.len() > N {
+            return Err(CapacityError::new(()));
         }
+        Ok(slice.iter().cloned().collect())
     }
 }
 
 // Build out iterator type for ArrayVec as ArrayVecIntoIter<T, N>
 // Consuming iterator (by-value): Moves out owned T.
 // But will provide iterators for fundamental types: & and &mut
-#[derive(Debug)]
 pub struct ArrayVecIntoIter<T, const N: usize> {
     values: [MaybeUninit<T>; N],
     len: usize,
     index: usize,
 }
 
+impl<T, const N: usize> ArrayVecIntoIter<T, N> {
+    /// Returns the elements not yet yielded as a slice.
+    /// SAFETY: Unsafe internally: `[index, len)` are init and not yet
+    /// moved out.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            core::slice::from_raw_parts(
+                self.values.as_ptr().add(self.index) as *const T,
+                self.len - self.index,
+            )
+        }
+    }
+
+    /// Returns the elements not yet yielded as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                self.values.as_mut_ptr().add(self.index) as *mut T,
+                self.len - self.index,
+            )
+        }
+    }
+}
+
 // Implement Iterator trait on ArrayVecIntoIter making it an iterator.
 impl<T, const N: usize> Iterator for ArrayVecIntoIter<T, N> {
     type Item = T;
@@ -147,6 +682,57 @@ impl<T, const N: usize> Iterator for ArrayVecIntoIter<T, N> {
         let remaining = self.len.saturating_sub(self.index);
         (remaining, Some(remaining))
     }
+
+    // Skips `n` items in one go, dropping them in place.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.min(self.len - self.index);
+        let start = self.index;
+        // SAFETY: Advance `index` before dropping, so a panicking `Drop`
+        // can't make our own Drop see (and drop) those slots again.
+        self.index += skip;
+        unsafe {
+            let skipped =
+                ptr::slice_from_raw_parts_mut(self.values.as_mut_ptr().add(start) as *mut T, skip);
+            ptr::drop_in_place(skipped);
+        }
+        self.next()
+    }
+
+    // No need to walk: remaining items are dropped with `self`.
+    fn count(self) -> usize {
+        self.len - self.index
+    }
+
+    // Reads just the last item; the rest are dropped with `self`.
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for ArrayVecIntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: Old `len - 1` is in `[index, len)`, so slot is init.
+        Some(unsafe { self.values[self.len].assume_init_read() })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for ArrayVecIntoIter<T, N> {}
+
+impl<T, const N: usize> core::iter::FusedIterator for ArrayVecIntoIter<T, N> {}
+
+// Clones only the remaining elements into a fresh iterator.
+impl<T, const N: usize> Clone for ArrayVecIntoIter<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let rest: ArrayVec<T, N> = self.as_slice().iter().cloned().collect();
+        rest.into_iter()
+    }
 }
 
 // Implement Drop trait to safely deallocate initialized elements
@@ -159,19 +745,37 @@ impl<T, const N: usize> Drop for ArrayVecIntoIter<T, N> {
         #[cfg(debug_assertions)]
         debug_assert!(self.index <= self.len);
 
-        // SAFETY: Invariant holds for those slots.
-        for i in self.index..self.len {
-            unsafe {
-                self.values[i].assume_init_drop();
-            }
+        // SAFETY: Invariant holds for those slots. Dropped as one slice
+        // (after marking them taken), so a panicking `Drop` still lets
+        // the others drop, and nothing is dropped twice.
+        let start = self.index;
+        self.index = self.len;
+        unsafe {
+            let rest = ptr::slice_from_raw_parts_mut(
+                self.values.as_mut_ptr().add(start) as *mut T,
+                self.len - start,
+            );
+            ptr::drop_in_place(rest);
         }
         // Uninit slots auto-drop as MaybeUninit: meaning as "garbage"
         // bytes they are overwritten by the next write.
     }
 }
 
+// Shows only the elements not yet yielded.
+impl<T, const N: usize> fmt::Debug for ArrayVecIntoIter<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayVecIntoIter")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
 // Implement IntoIterator for ArrayVecIntoIter; returns an iterator.
-impl<T, const N: usize> IntoIterator for ArrayVec<T, N> {
+impl<T, const N: usize, L: LenType> IntoIterator for ArrayVec<T, N, L> {
     type Item = T;
     type IntoIter = ArrayVecIntoIter<T, N>;
 
@@ -182,8 +786,10 @@ impl<T, const N: usize> IntoIterator for ArrayVec<T, N> {
 
         // SAFETY: Read fields out (valid as long as we don't access `this` after).
         // ptr::read performs bitwise copy without calling Drop on read memory
+        // (a no-op copy for ZSTs or `N == 0`; `len` still says how many
+        // elements the iterator owns).
         let values = unsafe { ptr::read(&this.values) };
-        let len = unsafe { ptr::read(&this.len) };
+        let len = this.len();
 
         ArrayVecIntoIter {
             values,
@@ -195,7 +801,7 @@ impl<T, const N: usize> IntoIterator for ArrayVec<T, N> {
 
 // Implement IntoIterator for fundamental types of ArrayVec: & and &mut.
 // By reference: yields &T
-impl<'a, T, const N: usize> IntoIterator for &'a ArrayVec<T, N> {
+impl<'a, T, const N: usize, L: LenType> IntoIterator for &'a ArrayVec<T, N, L> {
     type Item = &'a T;
     type IntoIter = core::slice::Iter<'a, T>;
 
@@ -205,7 +811,7 @@ impl<'a, T, const N: usize> IntoIterator for &'a ArrayVec<T, N> {
 }
 
 // By mutable reference: yields &mut T
-impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayVec<T, N> {
+impl<'a, T, const N: usize, L: LenType> IntoIterator for &'a mut ArrayVec<T, N, L> {
     type Item = &'a mut T;
     type IntoIter = core::slice::IterMut<'a, T>;
 
@@ -215,7 +821,11 @@ impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayVec<T, N> {
 }
 
 // Extending functionality: Implementing FromIterator.
-impl<T, const N: usize> FromIterator<T> for ArrayVec<T, N> {
+// Lossy: items past capacity are dropped without notice. Use
+// `ArrayVec::try_from_iter` to be told about them instead.
+// Panic safety: `arr_vec` is its own drop guard; every pushed item is
+// counted in `len`, so if the iterator panics they're all dropped.
+impl<T, const N: usize, L: LenType> FromIterator<T> for ArrayVec<T, N, L> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut arr_vec = Self::new();
         let iter = iter.into_iter();
@@ -231,21 +841,90 @@ impl<T, const N: usize> FromIterator<T> for ArrayVec<T, N> {
 
         // General case: Push until full (truncates excess)
         for item in iter {
-            let _ = arr_vec.try_push(item); // Err(item) dropped if full.
+            let _ = arr_vec.try_push(item); // Rejected item dropped if full.
         }
         arr_vec
     }
 }
 
 // Implement Extend for appending (up to "both" iter and ArrayVec capacity)
-impl<T, const N: usize> Extend<T> for ArrayVec<T, N> {
+// Lossy like FromIterator: stops at capacity. See `try_extend`.
+// Panic safety: if the iterator panics, the items pushed so far stay.
+impl<T, const N: usize, L: LenType> Extend<T> for ArrayVec<T, N, L> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         for item in iter {
             // Stop iterating when iter is finished.
-            if let Err(_) = self.try_push(item) {
+            if self.try_push(item).is_err() {
                 // break if self is full
                 break;
             }
         }
     }
 }
+
+// What's left of an iterator after an ArrayVec filled up: the item that
+// didn't fit, followed by everything not yet pulled from the iterator.
+pub type Leftover<I> = core::iter::Chain<core::iter::Once<<I as Iterator>::Item>, I>;
+
+// What `try_from_iter` hands back on overflow: the collection, filled to
+// capacity, plus the Leftover of the iterator.
+pub type Overflow<C, I> = (C, Leftover<I>);
+
+// Lossless counterparts to FromIterator and Extend.
+impl<T, const N: usize, L: LenType> ArrayVec<T, N, L> {
+    /// Collects `iter` into a new ArrayVec. If there are more than `N`
+    /// items, the error hands back both the full ArrayVec (the first `N`
+    /// items) and the leftover items, so nothing is dropped. Only pulls
+    /// one item past capacity, so it terminates on infinite iterators.
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<Overflow<Self, I::IntoIter>>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut arr_vec = Self::new();
+        match arr_vec.try_extend(iter) {
+            Ok(()) => Ok(arr_vec),
+            Err(err) => Err(CapacityError::new((arr_vec, err.element()))),
+        }
+    }
+
+    /// Pushes items from `iter` until it runs out or the ArrayVec is
+    /// full. On overflow, the items pushed so far stay, and the error
+    /// hands back the rejected item chained with the rest of `iter`, so
+    /// nothing is lost. Use `.count()` on it for just the overflow count.
+    /// If `iter` panics, the items pushed so far stay.
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), CapacityError<Leftover<I::IntoIter>>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        while let Some(item) = iter.next() {
+            if let Err(err) = self.try_push(item) {
+                let leftover = core::iter::once(err.element()).chain(iter);
+                return Err(CapacityError::new(leftover));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T, const N: usize, L: LenType> ArrayVec<T, N, L>
+where
+    T: Clone,
+{
+    /// Clones all of `other` onto the end, or nothing at all if it
+    /// doesn't fit. The error carries how many elements are too many.
+    /// If a `clone` panics, the clones made before it stay.
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError<usize>> {
+        let remaining = self.remaining_capacity();
+        if other.len() > remaining {
+            return Err(CapacityError::new(other.len() - remaining));
+        }
+        for item in other {
+            // Can't fail: checked above. If `clone` panics, the clones
+            // pushed so far stay in (init and counted by `len`); that's
+            // the one case where this isn't all-or-nothing.
+            let _ = self.try_push(item.clone());
+        }
+        Ok(())
+    }
+}
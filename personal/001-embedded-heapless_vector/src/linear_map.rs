@@ -0,0 +1,344 @@
+use core::borrow::Borrow;
+use core::fmt;
+
+use crate::map::MapStorage;
+use crate::{ArrayVec, ArrayVecIntoIter, CapacityError, Entry, MapIter, MapIterMut};
+
+// Fixed-capacity map stored as an ArrayVec of `(key, value)` pairs in
+// insertion order. Every lookup is a linear scan comparing keys with
+// `==`; for small tables (a few dozen entries) this beats hashing.
+pub struct LinearMap<K, V, const N: usize> {
+    entries: ArrayVec<(K, V), N>,
+}
+
+impl<K, V, const N: usize> LinearMap<K, V, N> {
+    /// Creates a new empty LinearMap.
+    pub const fn new() -> Self {
+        LinearMap {
+            entries: ArrayVec::new(),
+        }
+    }
+
+    /// Returns the number of entries.
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if no more entries fit.
+    pub const fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterates over `(&key, &value)` in insertion order.
+    pub fn iter(&self) -> MapIter<'_, K, V> {
+        MapIter::new(&self.entries)
+    }
+
+    /// Iterates over `(&key, &mut value)` in insertion order.
+    pub fn iter_mut(&mut self) -> MapIterMut<'_, K, V> {
+        MapIterMut::new(&mut self.entries)
+    }
+
+    /// Iterates over the keys in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Iterates over the values in insertion order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    /// Iterates mutably over the values in insertion order.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.entries.iter_mut().map(|(_, v)| v)
+    }
+}
+
+impl<K, V, const N: usize> LinearMap<K, V, N>
+where
+    K: Eq,
+{
+    fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.entries.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Returns a reference to the value for `key`, if present.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    /// Returns a mutable reference to the value for `key`, if present.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| &mut self.entries[i].1)
+    }
+
+    /// Returns `true` if `key` is present.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).is_some()
+    }
+
+    /// Inserts a pair. If `key` was present, its value is replaced in
+    /// place (keeping its position) and the old value is returned. If
+    /// the map is full, the pair is handed back in a `CapacityError`.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+        match self.entry(key) {
+            Entry::Occupied(mut e) => Ok(Some(e.insert(value))),
+            Entry::Vacant(e) => e.try_insert(value).map(|_| None),
+        }
+    }
+
+    /// Removes `key` and returns its value, if present. Later entries
+    /// shift down, so insertion order is kept.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes `key` and returns the stored pair, if present.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| self.entries.remove(i))
+    }
+
+    /// Gets the entry for `key` for in-place insert-or-update.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, Self> {
+        match self.position(&key) {
+            Some(index) => Entry::occupied(self, index),
+            None => Entry::vacant(self, key, ()),
+        }
+    }
+}
+
+// Removal shifts later pairs down, so insertion order survives.
+impl<K, V, const N: usize> MapStorage<K, V> for LinearMap<K, V, N> {
+    type Hint = ();
+
+    fn pairs(&self) -> &[(K, V)] {
+        &self.entries
+    }
+
+    fn pairs_mut(&mut self) -> &mut [(K, V)] {
+        &mut self.entries
+    }
+
+    fn remove_at(&mut self, index: usize) -> (K, V) {
+        self.entries.remove(index)
+    }
+
+    fn insert_new(&mut self, key: K, value: V, (): ()) -> Result<usize, CapacityError<(K, V)>> {
+        self.entries.try_push((key, value))?;
+        Ok(self.entries.len() - 1)
+    }
+}
+
+impl<K, V, const N: usize> Default for LinearMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize> Clone for LinearMap<K, V, N>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        LinearMap {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K, V, const N: usize> fmt::Debug for LinearMap<K, V, N>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Equal if they hold the same pairs, in any order.
+impl<K, V, const N: usize, const M: usize> PartialEq<LinearMap<K, V, M>> for LinearMap<K, V, N>
+where
+    K: Eq,
+    V: PartialEq,
+{
+    fn eq(&self, other: &LinearMap<K, V, M>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V, const N: usize> Eq for LinearMap<K, V, N>
+where
+    K: Eq,
+    V: Eq,
+{
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a LinearMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut LinearMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = MapIterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// Owned pairs come straight out of the backing ArrayVec.
+impl<K, V, const N: usize> IntoIterator for LinearMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = ArrayVecIntoIter<(K, V), N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+// Set counterpart of LinearMap: distinct values in insertion order.
+pub struct LinearSet<T, const N: usize> {
+    map: LinearMap<T, (), N>,
+}
+
+impl<T, const N: usize> LinearSet<T, N> {
+    /// Creates a new empty LinearSet.
+    pub const fn new() -> Self {
+        LinearSet {
+            map: LinearMap::new(),
+        }
+    }
+
+    /// Returns the number of values.
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Removes all values.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Iterates in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.map.keys()
+    }
+}
+
+impl<T, const N: usize> LinearSet<T, N>
+where
+    T: Eq,
+{
+    /// Adds a value. Returns `Ok(true)` if it was new, `Ok(false)` if
+    /// already present, or hands it back if the set is full.
+    pub fn insert(&mut self, value: T) -> Result<bool, CapacityError<T>> {
+        match self.map.entry(value) {
+            Entry::Occupied(_) => Ok(false),
+            Entry::Vacant(e) => e
+                .try_insert(())
+                .map(|_| true)
+                .map_err(|err| CapacityError::new(err.element().0)),
+        }
+    }
+
+    /// Returns `true` if `value` is in the set.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Removes `value`, returning whether it was present.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+}
+
+impl<T, const N: usize> Default for LinearSet<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Clone for LinearSet<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        LinearSet {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for LinearSet<T, N>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,368 @@
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+// End-of-list marker for `Link` indices.
+const NONE: usize = usize::MAX;
+
+// Neighbours of a slot in the recency list (`prev` is more recent).
+#[derive(Clone, Copy)]
+struct Link {
+    prev: usize,
+    next: usize,
+}
+
+// Fixed-capacity least-recently-used cache on `[MaybeUninit<(K, V)>; N]`
+// storage, with an index-based doubly linked list from most (`head`) to
+// least (`tail`) recently used. Lookups scan linearly, which for the
+// small `N` this is meant for beats hashing; reordering and eviction are
+// O(1).
+//
+// Invariant: slots `0..len` are init and each is on the list exactly
+// once; other slots are uninit. Removal moves the last slot into the
+// hole, so the init slots stay dense.
+pub struct LruCache<K, V, const N: usize> {
+    entries: [MaybeUninit<(K, V)>; N],
+    links: [Link; N],
+    head: usize,
+    tail: usize,
+    len: usize,
+}
+
+impl<K, V, const N: usize> LruCache<K, V, N> {
+    /// Creates a new empty LruCache.
+    pub const fn new() -> Self {
+        LruCache {
+            entries: [const { MaybeUninit::uninit() }; N],
+            links: [Link {
+                prev: NONE,
+                next: NONE,
+            }; N],
+            head: NONE,
+            tail: NONE,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of cached entries.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing is cached.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the next new key will evict an entry.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    // SAFETY (for callers): `index < len`, so the slot is init.
+    fn entry(&self, index: usize) -> &(K, V) {
+        debug_assert!(index < self.len);
+        unsafe { self.entries[index].assume_init_ref() }
+    }
+
+    fn entry_mut(&mut self, index: usize) -> &mut (K, V) {
+        debug_assert!(index < self.len);
+        unsafe { self.entries[index].assume_init_mut() }
+    }
+
+    // Takes slot `index` out of the recency list.
+    fn unlink(&mut self, index: usize) {
+        let Link { prev, next } = self.links[index];
+        match prev {
+            NONE => self.head = next,
+            prev => self.links[prev].next = next,
+        }
+        match next {
+            NONE => self.tail = prev,
+            next => self.links[next].prev = prev,
+        }
+    }
+
+    // Puts slot `index` (not on the list) at the most recent end.
+    fn link_front(&mut self, index: usize) {
+        self.links[index] = Link {
+            prev: NONE,
+            next: self.head,
+        };
+        match self.head {
+            NONE => self.tail = index,
+            head => self.links[head].prev = index,
+        }
+        self.head = index;
+    }
+
+    // Marks slot `index` as most recently used.
+    fn promote(&mut self, index: usize) {
+        if self.head != index {
+            self.unlink(index);
+            self.link_front(index);
+        }
+    }
+
+    // Moves the entry out of slot `index`, then fills the hole with the
+    // last slot (relinking its neighbours) to keep `0..len` dense.
+    fn remove_index(&mut self, index: usize) -> (K, V) {
+        self.unlink(index);
+        // SAFETY: `index < len`, so init; it's off the list and `len` is
+        // lowered below, so it's never read again.
+        let entry = unsafe { self.entries[index].assume_init_read() };
+        let last = self.len - 1;
+        if index != last {
+            // SAFETY: `last` is init; after the copy it's treated as
+            // uninit (`len` drops below it).
+            unsafe {
+                let base = self.entries.as_mut_ptr();
+                ptr::copy_nonoverlapping(base.add(last), base.add(index), 1);
+            }
+            let Link { prev, next } = self.links[last];
+            self.links[index] = self.links[last];
+            match prev {
+                NONE => self.head = index,
+                prev => self.links[prev].next = index,
+            }
+            match next {
+                NONE => self.tail = index,
+                next => self.links[next].prev = index,
+            }
+        }
+        self.len = last;
+        entry
+    }
+
+    /// Removes and returns the least recently used entry.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        if self.tail == NONE {
+            return None;
+        }
+        Some(self.remove_index(self.tail))
+    }
+
+    /// Returns the least recently used entry (the next to be evicted)
+    /// without promoting it.
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        if self.tail == NONE {
+            return None;
+        }
+        let (k, v) = self.entry(self.tail);
+        Some((k, v))
+    }
+
+    /// Drops every entry.
+    pub fn clear(&mut self) {
+        let len = self.len;
+        // Forget everything first, so a panicking `Drop` leaves a valid
+        // (empty) cache; the slice drop still drops the other entries.
+        self.len = 0;
+        self.head = NONE;
+        self.tail = NONE;
+        // SAFETY: slots `0..len` were init and are now outside `len`.
+        unsafe {
+            let init = ptr::slice_from_raw_parts_mut(self.entries.as_mut_ptr() as *mut (K, V), len);
+            ptr::drop_in_place(init);
+        }
+    }
+
+    /// Iterates over `(&key, &value)` from most to least recently used,
+    /// without promoting anything. `.rev()` goes the other way.
+    pub fn iter(&self) -> LruIter<'_, K, V, N> {
+        LruIter {
+            cache: self,
+            front: self.head,
+            back: self.tail,
+            remaining: self.len,
+        }
+    }
+}
+
+impl<K: Eq, V, const N: usize> LruCache<K, V, N> {
+    // Slot holding `key`, if cached.
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        (0..self.len).find(|&i| self.entry(i).0.borrow() == key)
+    }
+
+    /// Returns the value for `key` and marks it most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.promote(index);
+        Some(&self.entry(index).1)
+    }
+
+    /// Like `get`, but returns the value mutably.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.promote(index);
+        Some(&mut self.entry_mut(index).1)
+    }
+
+    /// Returns the value for `key` without changing its recency.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        Some(&self.entry(index).1)
+    }
+
+    /// Returns `true` if `key` is cached. Doesn't change its recency.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Inserts or updates `key`, making it the most recently used, and
+    /// returns the entry it displaced:
+    /// - `key` was cached: the old key and value it replaced.
+    /// - full: the least recently used entry, evicted to make room.
+    /// - otherwise: `None`.
+    ///
+    /// With `N == 0` nothing can be cached and the pair comes straight
+    /// back.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if N == 0 {
+            return Some((key, value));
+        }
+        let index = match self.find(&key) {
+            Some(index) => index,
+            None if self.len == N => self.tail,
+            None => {
+                let index = self.len;
+                self.entries[index].write((key, value));
+                self.len += 1;
+                self.link_front(index);
+                return None;
+            }
+        };
+        let old = core::mem::replace(self.entry_mut(index), (key, value));
+        self.promote(index);
+        Some(old)
+    }
+
+    /// Removes `key` and returns its value, if cached.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        Some(self.remove_index(index).1)
+    }
+}
+
+impl<K, V, const N: usize> Default for LruCache<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize> Drop for LruCache<K, V, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// Keeps the recency order. Cloned oldest first, so each `put` moves the
+// entry to the front and the newest ends up there.
+impl<K, V, const N: usize> Clone for LruCache<K, V, N>
+where
+    K: Clone + Eq,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut cache = LruCache::new();
+        for (k, v) in self.iter().rev() {
+            // Can't evict: same capacity, distinct keys.
+            cache.put(k.clone(), v.clone());
+        }
+        cache
+    }
+}
+
+// Shown as a map, most recently used first.
+impl<K, V, const N: usize> fmt::Debug for LruCache<K, V, N>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a LruCache<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = LruIter<'a, K, V, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Iterator over the entries in recency order, from `LruCache::iter`.
+pub struct LruIter<'a, K, V, const N: usize> {
+    cache: &'a LruCache<K, V, N>,
+    front: usize,
+    back: usize,
+    remaining: usize,
+}
+
+impl<'a, K, V, const N: usize> Iterator for LruIter<'a, K, V, N> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (k, v) = self.cache.entry(self.front);
+        self.front = self.cache.links[self.front].next;
+        self.remaining -= 1;
+        Some((k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V, const N: usize> DoubleEndedIterator for LruIter<'_, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (k, v) = self.cache.entry(self.back);
+        self.back = self.cache.links[self.back].prev;
+        self.remaining -= 1;
+        Some((k, v))
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for LruIter<'_, K, V, N> {}
+
+impl<K, V, const N: usize> FusedIterator for LruIter<'_, K, V, N> {}
@@ -6,10 +6,22 @@
 // Only using stdlib to print to stdout & stderr for debugging
 extern crate std;
 
-use heapless_vector::ArrayVec;
+use core::fmt::Write;
+
+use heapless_vector::{
+    ArrayDeque, ArrayString, ArrayVec, BinaryHeap, Descending, IndexMap, LinearMap, LruCache, Min,
+    Slab, SortedArrayVec,
+};
 
 const CAP: usize = 5;
 
+// Lookup table of partially filled ArrayVecs, built at compile time.
+static PIN_GROUPS: [ArrayVec<u8, 4>; 3] = [
+    ArrayVec::from_array_len([2, 3, 0, 0], 2),
+    ArrayVec::from_array_len([8, 9, 10, 11], 4),
+    ArrayVec::new(),
+];
+
 fn main() {
     {
         // A:
@@ -36,15 +48,16 @@ fn main() {
         std::println!("ArrayVec `len` after pop: {}", arr_vec.len());
 
         // TEST: Add more elements beyond `CAP` size for ArrayVec;
-        // `try_push` should escape and return with Err.
+        // `try_push` should escape and return with a CapacityError
+        // carrying the rejected value.
         let arr_len = arr_vec.len();
         count = *arr_vec.get(arr_len - 1).unwrap();
         let mut arr_err_els: ArrayVec<Result<(), i32>, CAP> = ArrayVec::new();
 
         for _ in arr_len..(CAP * 2) {
             count += 1;
-            if let Err(value) = arr_vec.try_push(count) {
-                arr_err_els.try_push(Err(value)).unwrap();
+            if let Err(err) = arr_vec.try_push(count) {
+                arr_err_els.try_push(Err(err.element())).unwrap();
             }
         }
         std::println!("---\nFilled ArrayVec: {:?}", arr_vec.as_slice());
@@ -103,7 +116,7 @@ fn main() {
         // Above code confirms `impl IntoIterator for ArrayVec` safety invariants.
 
         std::println!("---\n{:?}", arr_iter); /* View created ArrayVecIntoIter.
-        See `len` and `index` fields. */
+        Debug shows only the elements left to yield. */
 
         let mut arr_vec2: ArrayVec<Option<u8>, CAP> = ArrayVec::new();
         loop {
@@ -120,8 +133,8 @@ fn main() {
                 }
             }
         }
-        std::println!("{:?}", arr_iter); /* Review `len` and `index` fields.
-        Notice index incr caused by calling `next()` on ArrayVecIntoIter. */
+        std::println!("{:?}", arr_iter); /* Nothing left: every call to
+        `next()` on ArrayVecIntoIter moved one element out. */
         std::println!("{:?}", arr_vec2.as_slice());
     }
 
@@ -134,8 +147,8 @@ fn main() {
         std::println!("---\n{:?}", arr_vec.as_slice());
 
         // Using `from_iter`:
-        let arr_vec = ArrayVec::<_, 5>::from_iter(-3..5 as i8); /* Using
-        type inference for `T` in `ArrayVec<T, N>` helped by type cast
+        let arr_vec = ArrayVec::<_, 5>::from_iter(-3..5_i8); /* Using
+        type inference for `T` in `ArrayVec<T, N>` helped by type suffix
         on iterator. */
         std::println!("{:?}", arr_vec.as_slice());
     }
@@ -175,11 +188,8 @@ fn main() {
 
         arr_vec1.extend(arr_vec2);
 
-        let mut empty_arr_vec: ArrayVecCap10<Option<u8>> = ArrayVec::new();
-        std::println!("{:?}", arr_vec1.show_init(&mut empty_arr_vec));
-        drop(empty_arr_vec); /* Choosing to explicity drop (or free, or
-        deallocate) the stack memory consumed by `empty_arr_vec` here
-        as it's no longer in use. */
+        // `show_init` borrows `arr_vec1`; no second ArrayVec needed.
+        std::println!("{:?}", arr_vec1.show_init());
 
         // Scenario 2:
         let mut arr_vec1: ArrayVecCap5<i8> = ArrayVec::new();
@@ -194,11 +204,295 @@ fn main() {
 
         arr_vec1.extend(arr_vec2);
 
-        let mut empty_arr_vec: ArrayVecCap5<Option<i8>> = ArrayVec::new();
-        std::println!("{:?}", arr_vec1.show_init(&mut empty_arr_vec));
-        /* Don't need to explicity drop `empty_arr_vec` here as before
-        because this is the end of the scope, and the Rust Compiler (rustc)
-        will call the drop method in ArrayVec's destructor `Drop`
-        to drop `empty_arr_vec` implicity. */
+        std::println!("{:?}", arr_vec1.show_init());
+        std::println!("{:#?}", arr_vec1); // Alternate mode adds capacity.
+
+        // Lossless alternative: `try_extend` hands back what didn't fit.
+        let mut arr_vec1: ArrayVecCap5<i8> = ArrayVec::new();
+        if let Err(err) = arr_vec1.try_extend(-7..0) {
+            let leftover: ArrayVecCap5<i8> = err.element().collect();
+            std::println!("Extended: {:?}, left over: {:?}", arr_vec1, leftover);
+        }
+    }
+
+    {
+        // F:
+        // Positional insert/remove on ArrayVec (shifting init slots).
+        let mut arr_vec = ArrayVec::<u8, CAP>::new();
+        for i in [10, 20, 40] {
+            arr_vec.try_push(i).unwrap();
+        }
+
+        // Insert in the middle keeps the window sorted.
+        arr_vec.try_insert(2, 30).unwrap();
+        arr_vec.try_insert(0, 0).unwrap();
+        std::println!("---\nAfter inserts: {:?}", arr_vec.as_slice());
+
+        // Full: value is handed back, just like `try_push`.
+        std::println!("Insert when full: {:?}", arr_vec.try_insert(1, 5));
+
+        std::println!("Removed: {}", arr_vec.remove(1));
+        std::println!("Swap-removed: {}", arr_vec.swap_remove(0));
+        std::println!("After removes: {:?}", arr_vec.as_slice());
+
+        arr_vec.truncate(1);
+        std::println!("Truncated: {:?}", arr_vec.as_slice());
+        arr_vec.clear();
+        std::println!("Cleared, is empty: {}", arr_vec.is_empty());
+    }
+
+    {
+        // G:
+        // Drain a contiguous batch out of an ArrayVec without consuming it.
+        let mut queue: ArrayVec<u8, 10> = (1..=10).collect();
+
+        // Flush the first 4 queued packets.
+        std::println!("---");
+        for packet in queue.drain(..4) {
+            std::println!("Flushed packet: {}", packet);
+        }
+        std::println!("Still queued: {:?}", queue.as_slice());
+
+        // Partially consumed drains drop the rest and close the gap.
+        let mut batch = queue.drain(1..4);
+        std::println!("Last of batch: {:?}", batch.next_back());
+        drop(batch);
+        std::println!("After partial drain: {:?}", queue.as_slice());
+    }
+
+    {
+        // H:
+        // Filter and dedup in place (no second ArrayVec on the stack).
+        let mut readings: ArrayVec<i16, 10> =
+            [3, 3, -1, 7, 7, 7, -4, 2, 2, 9].into_iter().collect();
+
+        readings.dedup();
+        std::println!("---\nDeduped: {:?}", readings.as_slice());
+
+        readings.retain(|&r| r >= 0);
+        std::println!("Non-negative: {:?}", readings.as_slice());
+
+        readings.as_mut_slice().sort();
+        readings.dedup_by_key(|r| *r / 5);
+        std::println!("One per bucket of 5: {:?}", readings.as_slice());
+    }
+
+    {
+        // I:
+        // Format a log line into a fixed-capacity UTF-8 string.
+        let mut line = ArrayString::<32>::new();
+        write!(line, "temp={}C id={:#04x}", 21, 7).unwrap();
+        line.try_push(' ').unwrap();
+        line.try_push_str("ok ✓").unwrap();
+        std::println!("---\nLog line: {} ({} bytes)", line, line.len());
+
+        // Overflow is reported instead of silently cutting the line.
+        // `write!` may have pushed some pieces before failing, so roll back.
+        let before = line.len();
+        std::println!("Too long: {:?}", write!(line, "{:>20}", "overflow"));
+        line.truncate(before);
+
+        // Truncating must land on a char boundary.
+        line.truncate(line.len() - "✓".len());
+        std::println!("Truncated: {:?}", line);
+
+        let frame = ArrayString::<4>::try_from("ACK");
+        std::println!("From &str: {:?}", frame);
+        std::println!(
+            "From &str (too long): {:?}",
+            ArrayString::<2>::try_from("NACK")
+        );
+    }
+
+    {
+        // J:
+        // ArrayVecs in a `static` table (const construction).
+        std::println!("---");
+        for (i, group) in PIN_GROUPS.iter().enumerate() {
+            std::println!("Pin group {}: {:?} (empty: {})", i, group, group.is_empty());
+        }
+    }
+
+    {
+        // K:
+        // DMA-style receive: write into the uninit tail, then commit.
+        let mut rx_buf = ArrayVec::<u8, 8>::new();
+        rx_buf.try_push(0xAA).unwrap(); // Header already received.
+
+        // Stand-in for the peripheral filling the spare slots.
+        let spare = rx_buf.spare_capacity_mut();
+        let received = [0x01, 0x02, 0x03];
+        for (slot, byte) in spare.iter_mut().zip(received) {
+            slot.write(byte);
+        }
+        // SAFETY: The 3 slots after `len` were just written above.
+        unsafe { rx_buf.set_len(rx_buf.len() + received.len()) };
+
+        std::println!("---\nRx buffer: {:x?}", rx_buf);
+        std::println!(
+            "Remaining: {}, full: {}",
+            rx_buf.remaining_capacity(),
+            rx_buf.is_full()
+        );
+    }
+
+    {
+        // L:
+        // Compact length type: store `len` in a u8 for small buffers.
+        let mut small = ArrayVec::<u8, 16, u8>::new();
+        small.try_extend(b"sensor".iter().copied()).unwrap();
+        std::println!(
+            "---\nu8 len: {} bytes, usize len: {} bytes, contents: {:?}",
+            core::mem::size_of::<ArrayVec<u8, 16, u8>>(),
+            core::mem::size_of::<ArrayVec<u8, 16>>(),
+            small
+        );
+        // `ArrayVec::<u8, 256, u8>::new()` would fail to compile:
+        // 256 doesn't fit in a u8.
+    }
+
+    {
+        // M:
+        // Ring buffer: FIFO for a UART receive path, plus a rolling log.
+        let mut rx = ArrayDeque::<u8, 4>::new();
+        for byte in b"AT\r" {
+            rx.try_push_back(*byte).unwrap();
+        }
+        std::println!("---\nRx FIFO: {:?}", rx);
+        std::println!("Popped front: {:?}", rx.pop_front());
+
+        // Wraps around the end of storage; `as_slices` shows both halves.
+        rx.try_push_back(b'\n').unwrap();
+        rx.try_push_back(b'O').unwrap();
+        std::println!("Wrapped halves: {:?}", rx.as_slices());
+
+        // Overwrite-oldest: once full, each push evicts the front.
+        let mut log = ArrayDeque::<u16, 3>::new();
+        for reading in [100, 101, 102, 103, 104] {
+            if let Some(evicted) = log.push_back_overwrite(reading) {
+                std::println!("Evicted: {}", evicted);
+            }
+        }
+        std::println!("Rolling log: {:?}", log);
+    }
+
+    {
+        // N:
+        // Priority queue for a cooperative scheduler: (priority, task id).
+        let mut ready = BinaryHeap::<(u8, u8), 4>::new();
+        for task in [(1, 10), (3, 11), (2, 12), (3, 13)] {
+            ready.try_push(task).unwrap();
+        }
+        std::println!("---\nFull: {:?}", ready.try_push((9, 14)));
+
+        // Demote the top task in place; the heap re-sifts on drop.
+        if let Some(mut top) = ready.peek_mut() {
+            top.0 = 0;
+        }
+        while let Some((priority, id)) = ready.pop() {
+            std::println!("Run task {} (priority {})", id, priority);
+        }
+
+        // Min-heap: earliest deadline first.
+        let mut deadlines = BinaryHeap::<u32, 4, Min>::new();
+        for ms in [40, 10, 30] {
+            deadlines.try_push(ms).unwrap();
+        }
+        std::println!("Next deadline: {:?}", deadlines.peek());
+        std::println!("Sorted (latest first): {:?}", deadlines.into_sorted_vec());
+    }
+
+    {
+        // O:
+        // Small config table: linear scan beats hashing at this size.
+        let mut config = LinearMap::<&str, u32, 4>::new();
+        config.insert("baud", 9600).unwrap();
+        config.insert("retries", 3).unwrap();
+        config.insert("baud", 115_200).unwrap();
+        *config.entry("retries").or_insert(0).unwrap() += 1;
+        std::println!("---\nConfig: {:?}", config);
+
+        // Register name -> address lookup, hashed.
+        let mut regs = IndexMap::<&str, u16, 8>::new();
+        for (name, addr) in [("CTRL", 0x00), ("STATUS", 0x04), ("DATA", 0x08)] {
+            regs.insert(name, addr).unwrap();
+        }
+        regs.remove("CTRL");
+        std::println!("Registers: {:?}", regs);
+        std::println!("DATA at {:#04x}", regs.get("DATA").unwrap());
+    }
+
+    {
+        // P:
+        // Timer pool: handles stay valid until the timer is cancelled,
+        // and a stale handle can't reach a timer that reused the slot.
+        let mut timers = Slab::<u32, 4>::new();
+        let blink = timers.try_insert(500).unwrap();
+        let watchdog = timers.try_insert(1000).unwrap();
+        timers.remove(blink);
+        let debounce = timers.try_insert(20).unwrap();
+        std::println!("---\nTimers: {:?}", timers);
+        std::println!("Stale blink handle: {:?}", timers.get(blink));
+        std::println!(
+            "Debounce reused slot {}: {:?}",
+            debounce.index(),
+            timers.get(debounce)
+        );
+        if let Some(ms) = timers.get_mut(watchdog) {
+            *ms *= 2;
+        }
+        for (handle, ms) in &timers {
+            std::println!("  slot {} -> {} ms", handle.index(), ms);
+        }
+    }
+
+    // Q: only with `--features alloc` (or `std`).
+    #[cfg(feature = "alloc")]
+    {
+        // Host-side tool: usually a handful of args, but never reject more.
+        let mut args = heapless_vector::SmallVec::<&str, 2>::new();
+        args.push("--port");
+        args.push("/dev/ttyUSB0");
+        std::println!("---\nArgs {:?}, spilled: {}", args, args.spilled());
+        args.push("--verbose");
+        std::println!("Args {:?}, spilled: {}", args, args.spilled());
+        args.truncate(2);
+        args.shrink_to_fit();
+        std::println!("Shrunk back inline: {}", !args.spilled());
+    }
+
+    {
+        // R:
+        // Top-3 temperature readings, hottest first. Once full, a new
+        // reading only gets in by evicting the coolest one kept.
+        let mut hottest = SortedArrayVec::<i16, 3, Descending>::new();
+        std::println!("---");
+        for reading in [21, 35, 18, 40, 29, 33] {
+            if let Some(dropped) = hottest.insert_evicting_last(reading) {
+                std::println!("Dropped {}", dropped);
+            }
+        }
+        std::println!("Hottest: {:?}", hottest);
+        std::println!("Saw 40? {}", hottest.contains(&40));
+
+        // Merge another sensor's top 3; past capacity spills out.
+        let other = SortedArrayVec::<i16, 3, Descending>::from(ArrayVec::from([38, 12, 31]));
+        let (merged, rest) = hottest.merge(other);
+        std::println!("Merged: {:?}, didn't fit: {:?}", merged, rest);
+    }
+
+    {
+        // S:
+        // Decoded calibration blocks by sensor id; room for two.
+        let mut calibration = LruCache::<u8, [i16; 2], 2>::new();
+        calibration.put(1, [10, -3]);
+        calibration.put(2, [7, 0]);
+        // Reading sensor 1 makes sensor 2 the least recently used...
+        std::println!("---\nSensor 1: {:?}", calibration.get(&1));
+        // ...so decoding sensor 3 evicts it.
+        std::println!("Evicted: {:?}", calibration.put(3, [0, 5]));
+        std::println!("Peek 1 (no promote): {:?}", calibration.peek(&1));
+        std::println!("Most recent first: {:?}", calibration);
+        std::println!("Pop LRU: {:?}", calibration.pop_lru());
     }
 }
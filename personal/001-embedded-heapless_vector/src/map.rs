@@ -0,0 +1,277 @@
+// Entry API and borrowing iterators shared by LinearMap and IndexMap.
+// Both keep their pairs densely in an ArrayVec; they only differ in how
+// keys are found and how an entry is removed or added, which is what
+// `MapStorage` abstracts.
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::slice;
+
+use crate::CapacityError;
+
+mod sealed {
+    use crate::CapacityError;
+
+    // Implemented by the maps in this crate only.
+    pub trait MapStorage<K, V> {
+        /// What a vacant entry remembers from the failed lookup, so
+        /// inserting doesn't redo it (the hash, for IndexMap).
+        type Hint: Copy;
+
+        /// The stored pairs, in iteration order.
+        fn pairs(&self) -> &[(K, V)];
+
+        /// The stored pairs, mutably. Keys must not be changed.
+        fn pairs_mut(&mut self) -> &mut [(K, V)];
+
+        /// Removes and returns the pair at `index`.
+        fn remove_at(&mut self, index: usize) -> (K, V);
+
+        /// Adds a pair for a key known to be absent and returns its
+        /// index, or hands the pair back if full.
+        fn insert_new(
+            &mut self,
+            key: K,
+            value: V,
+            hint: Self::Hint,
+        ) -> Result<usize, CapacityError<(K, V)>>;
+    }
+}
+
+pub(crate) use sealed::MapStorage;
+
+// A view into one entry of a map, from `LinearMap::entry` or
+// `IndexMap::entry`.
+pub enum Entry<'a, K, V, M: MapStorage<K, V>> {
+    Occupied(OccupiedEntry<'a, K, V, M>),
+    Vacant(VacantEntry<'a, K, V, M>),
+}
+
+pub struct OccupiedEntry<'a, K, V, M: MapStorage<K, V>> {
+    map: &'a mut M,
+    index: usize,
+    _pair: PhantomData<fn() -> (K, V)>,
+}
+
+pub struct VacantEntry<'a, K, V, M: MapStorage<K, V>> {
+    map: &'a mut M,
+    key: K,
+    hint: M::Hint,
+}
+
+impl<'a, K: 'a, V: 'a, M: MapStorage<K, V>> Entry<'a, K, V, M> {
+    // For the maps' `entry` methods, after their lookup.
+    pub(crate) fn occupied(map: &'a mut M, index: usize) -> Self {
+        Entry::Occupied(OccupiedEntry {
+            map,
+            index,
+            _pair: PhantomData,
+        })
+    }
+
+    pub(crate) fn vacant(map: &'a mut M, key: K, hint: M::Hint) -> Self {
+        Entry::Vacant(VacantEntry { map, key, hint })
+    }
+
+    /// Returns the entry's key.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => e.key(),
+        }
+    }
+
+    /// Returns the value, inserting `default` first if vacant. Fails
+    /// with `default` handed back if vacant and the map is full.
+    pub fn or_insert(self, default: V) -> Result<&'a mut V, CapacityError<V>> {
+        match self {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => e.insert(default),
+        }
+    }
+
+    /// Like `or_insert`, but only builds the value if vacant. On a full
+    /// map the built value is handed back.
+    pub fn or_insert_with<F>(self, default: F) -> Result<&'a mut V, CapacityError<V>>
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => e.insert(default()),
+        }
+    }
+
+    /// Runs `f` on the value if occupied.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(e) = &mut self {
+            f(e.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: 'a, V: 'a, M: MapStorage<K, V>> OccupiedEntry<'a, K, V, M> {
+    /// Returns the entry's key.
+    pub fn key(&self) -> &K {
+        &self.map.pairs()[self.index].0
+    }
+
+    /// Returns the value.
+    pub fn get(&self) -> &V {
+        &self.map.pairs()[self.index].1
+    }
+
+    /// Returns the value mutably.
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.pairs_mut()[self.index].1
+    }
+
+    /// Converts into a mutable reference tied to the map's borrow.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.pairs_mut()[self.index].1
+    }
+
+    /// Replaces the value, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry, returning its value. Other entries move as
+    /// for the map's own `remove`.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Removes the entry, returning the stored pair.
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_at(self.index)
+    }
+}
+
+impl<'a, K: 'a, V: 'a, M: MapStorage<K, V>> VacantEntry<'a, K, V, M> {
+    /// Returns the key that would be inserted.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes back the key without inserting anything.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the value, or hands it back if the map is full.
+    pub fn insert(self, value: V) -> Result<&'a mut V, CapacityError<V>> {
+        self.try_insert(value)
+            .map_err(|err| CapacityError::new(err.element().1))
+    }
+
+    // Like `insert`, but a full map hands back the key too.
+    pub(crate) fn try_insert(self, value: V) -> Result<&'a mut V, CapacityError<(K, V)>> {
+        let index = self.map.insert_new(self.key, value, self.hint)?;
+        Ok(&mut self.map.pairs_mut()[index].1)
+    }
+}
+
+// Iterator over `(&K, &V)`, in the map's iteration order.
+pub struct MapIter<'a, K, V> {
+    inner: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> MapIter<'a, K, V> {
+    pub(crate) fn new(pairs: &'a [(K, V)]) -> Self {
+        MapIter {
+            inner: pairs.iter(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for MapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for MapIter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for MapIter<'_, K, V> {}
+
+impl<K, V> FusedIterator for MapIter<'_, K, V> {}
+
+impl<K, V> Clone for MapIter<'_, K, V> {
+    fn clone(&self) -> Self {
+        MapIter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for MapIter<'_, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.clone()).finish()
+    }
+}
+
+// Iterator over `(&K, &mut V)`, in the map's iteration order. Keys stay
+// shared so they can't be changed under the map's index.
+pub struct MapIterMut<'a, K, V> {
+    inner: slice::IterMut<'a, (K, V)>,
+}
+
+impl<'a, K, V> MapIterMut<'a, K, V> {
+    pub(crate) fn new(pairs: &'a mut [(K, V)]) -> Self {
+        MapIterMut {
+            inner: pairs.iter_mut(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for MapIterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for MapIterMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, v)| (&*k, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for MapIterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for MapIterMut<'_, K, V> {}
+
+impl<K, V> fmt::Debug for MapIterMut<'_, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self.inner.as_slice().iter().map(|(k, v)| (k, v));
+        f.debug_map().entries(pairs).finish()
+    }
+}
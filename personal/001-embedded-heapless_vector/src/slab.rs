@@ -0,0 +1,320 @@
+use core::fmt;
+use core::iter::{Enumerate, FusedIterator, Zip};
+use core::mem::MaybeUninit;
+use core::slice;
+
+use crate::CapacityError;
+
+// Stable key for a value in a Slab. The generation is bumped every time
+// the slot is filled or emptied, so a handle to a removed value never
+// matches whatever later reuses its slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+impl Handle {
+    /// Returns the slot index the handle points at.
+    pub const fn index(self) -> usize {
+        self.index
+    }
+
+    /// Returns the generation the handle was issued for.
+    pub const fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+// Per-slot bookkeeping. Odd generation = occupied, even = vacant; the
+// generation wraps after 2^31 reuses of one slot, which we accept.
+// `next_free` is only meaningful while vacant (`N` ends the list).
+#[derive(Clone, Copy)]
+struct Meta {
+    generation: u32,
+    next_free: usize,
+}
+
+impl Meta {
+    const fn is_occupied(self) -> bool {
+        self.generation & 1 == 1
+    }
+}
+
+// Fixed-capacity object pool with generational handles, on
+// `[MaybeUninit<T>; N]` storage. Vacant slots form an intrusive free
+// list threaded through `meta`, so insert and remove are O(1);
+// iteration walks all `N` slots.
+//
+// Invariant: slot `i` is init iff `meta[i]` is occupied, and the free
+// list links exactly the vacant slots.
+pub struct Slab<T, const N: usize> {
+    values: [MaybeUninit<T>; N],
+    meta: [Meta; N],
+    free_head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> Slab<T, N> {
+    /// Creates a new empty Slab.
+    pub const fn new() -> Self {
+        let mut meta = [Meta {
+            generation: 0,
+            next_free: N,
+        }; N];
+        let mut i = 0;
+        while i < N {
+            meta[i].next_free = i + 1;
+            i += 1;
+        }
+        Slab {
+            values: [const { MaybeUninit::uninit() }; N],
+            meta,
+            free_head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of occupied slots.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no slots are occupied.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if every slot is occupied.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Stores `value` in a free slot and returns its handle, or hands
+    /// `value` back in a `CapacityError` if full.
+    pub fn try_insert(&mut self, value: T) -> Result<Handle, CapacityError<T>> {
+        let index = self.free_head;
+        if index == N {
+            return Err(CapacityError::new(value));
+        }
+        let meta = &mut self.meta[index];
+        self.free_head = meta.next_free;
+        meta.generation = meta.generation.wrapping_add(1);
+        self.values[index].write(value);
+        self.len += 1;
+        Ok(Handle {
+            index,
+            generation: meta.generation,
+        })
+    }
+
+    /// Returns `true` if `handle` still refers to a live value.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.meta
+            .get(handle.index)
+            .is_some_and(|meta| meta.generation == handle.generation && meta.is_occupied())
+    }
+
+    /// Returns a reference to the value behind `handle`, or `None` if it
+    /// has been removed (even if the slot has since been reused).
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        if !self.contains(handle) {
+            return None;
+        }
+        // SAFETY: occupied slots are init.
+        Some(unsafe { self.values[handle.index].assume_init_ref() })
+    }
+
+    /// Returns a mutable reference to the value behind `handle`, or
+    /// `None` if it has been removed.
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        if !self.contains(handle) {
+            return None;
+        }
+        // SAFETY: occupied slots are init.
+        Some(unsafe { self.values[handle.index].assume_init_mut() })
+    }
+
+    /// Removes and returns the value behind `handle`, or `None` if it
+    /// was already removed. The handle is dead afterwards.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        if !self.contains(handle) {
+            return None;
+        }
+        // SAFETY: occupied, so init; vacate_slot marks it uninit.
+        let value = unsafe { self.values[handle.index].assume_init_read() };
+        self.vacate_slot(handle.index);
+        Some(value)
+    }
+
+    // Marks an occupied slot vacant and pushes it on the free list. The
+    // value must already have been moved out (or be about to be dropped).
+    fn vacate_slot(&mut self, index: usize) {
+        let meta = &mut self.meta[index];
+        meta.generation = meta.generation.wrapping_add(1);
+        meta.next_free = self.free_head;
+        self.free_head = index;
+        self.len -= 1;
+    }
+
+    /// Drops every value. All outstanding handles become dead.
+    pub fn clear(&mut self) {
+        for index in 0..N {
+            if self.meta[index].is_occupied() {
+                // Vacate first: if the drop panics the slab is still
+                // consistent and this value won't be dropped again.
+                self.vacate_slot(index);
+                // SAFETY: was occupied, so init; now marked vacant.
+                unsafe { self.values[index].assume_init_drop() };
+            }
+        }
+    }
+
+    /// Iterates over `(handle, &value)` for occupied slots, in slot order.
+    pub fn iter(&self) -> SlabIter<'_, T> {
+        SlabIter {
+            slots: self.values.iter().zip(self.meta.iter()).enumerate(),
+            remaining: self.len,
+        }
+    }
+
+    /// Iterates over `(handle, &mut value)` for occupied slots, in slot
+    /// order.
+    pub fn iter_mut(&mut self) -> SlabIterMut<'_, T> {
+        SlabIterMut {
+            slots: self.values.iter_mut().zip(self.meta.iter()).enumerate(),
+            remaining: self.len,
+        }
+    }
+}
+
+impl<T, const N: usize> Default for Slab<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for Slab<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// Clones keep the same slots and generations, so handles from the
+// original are valid on the clone too.
+impl<T: Clone, const N: usize> Clone for Slab<T, N> {
+    fn clone(&self) -> Self {
+        let mut out = Slab::new();
+        for (handle, value) in self {
+            out.values[handle.index].write(value.clone());
+            out.meta[handle.index].generation = handle.generation;
+            out.len += 1;
+        }
+        // Generations of vacant slots matter too, or stale handles could
+        // come back to life once the clone reuses the slot.
+        for (dst, src) in out.meta.iter_mut().zip(self.meta.iter()) {
+            *dst = *src;
+        }
+        out.free_head = self.free_head;
+        out
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for Slab<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Slab<T, N> {
+    type Item = (Handle, &'a T);
+    type IntoIter = SlabIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut Slab<T, N> {
+    type Item = (Handle, &'a mut T);
+    type IntoIter = SlabIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// Iterator over occupied slots, from `Slab::iter`.
+pub struct SlabIter<'a, T> {
+    slots: Enumerate<Zip<slice::Iter<'a, MaybeUninit<T>>, slice::Iter<'a, Meta>>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for SlabIter<'a, T> {
+    type Item = (Handle, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        for (index, (value, meta)) in self.slots.by_ref() {
+            if meta.is_occupied() {
+                self.remaining -= 1;
+                let handle = Handle {
+                    index,
+                    generation: meta.generation,
+                };
+                // SAFETY: occupied slots are init.
+                return Some((handle, unsafe { value.assume_init_ref() }));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for SlabIter<'_, T> {}
+impl<T> FusedIterator for SlabIter<'_, T> {}
+
+// Mutable iterator over occupied slots, from `Slab::iter_mut`.
+pub struct SlabIterMut<'a, T> {
+    slots: Enumerate<Zip<slice::IterMut<'a, MaybeUninit<T>>, slice::Iter<'a, Meta>>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for SlabIterMut<'a, T> {
+    type Item = (Handle, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        for (index, (value, meta)) in self.slots.by_ref() {
+            if meta.is_occupied() {
+                self.remaining -= 1;
+                let handle = Handle {
+                    index,
+                    generation: meta.generation,
+                };
+                // SAFETY: occupied slots are init.
+                return Some((handle, unsafe { value.assume_init_mut() }));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for SlabIterMut<'_, T> {}
+impl<T> FusedIterator for SlabIterMut<'_, T> {}
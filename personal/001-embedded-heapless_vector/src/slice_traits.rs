@@ -0,0 +1,127 @@
+// Standard traits for the slice-backed vectors (ArrayVec, SmallVec),
+// written once. Each goes through the type's `as_slice`/`as_mut_slice`,
+// so only the init elements count and where they're stored never does:
+// equality, ordering and hashing all match `[T]`, which `Borrow<[T]>`
+// requires.
+//
+// Usage: `impl_slice_traits!([<generics after T>] Type<T, ...>);`. The
+// element type parameter must be called `T`.
+macro_rules! impl_slice_traits {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<T, $($generics)*> core::ops::Deref for $ty {
+            type Target = [T];
+
+            fn deref(&self) -> &[T] {
+                self.as_slice()
+            }
+        }
+
+        impl<T, $($generics)*> core::ops::DerefMut for $ty {
+            fn deref_mut(&mut self) -> &mut [T] {
+                self.as_mut_slice()
+            }
+        }
+
+        impl<T, I, $($generics)*> core::ops::Index<I> for $ty
+        where
+            I: core::slice::SliceIndex<[T]>,
+        {
+            type Output = I::Output;
+
+            fn index(&self, index: I) -> &Self::Output {
+                &self.as_slice()[index]
+            }
+        }
+
+        impl<T, I, $($generics)*> core::ops::IndexMut<I> for $ty
+        where
+            I: core::slice::SliceIndex<[T]>,
+        {
+            fn index_mut(&mut self, index: I) -> &mut Self::Output {
+                &mut self.as_mut_slice()[index]
+            }
+        }
+
+        impl<T, $($generics)*> AsRef<[T]> for $ty {
+            fn as_ref(&self) -> &[T] {
+                self.as_slice()
+            }
+        }
+
+        impl<T, $($generics)*> AsMut<[T]> for $ty {
+            fn as_mut(&mut self) -> &mut [T] {
+                self.as_mut_slice()
+            }
+        }
+
+        impl<T, $($generics)*> core::borrow::Borrow<[T]> for $ty {
+            fn borrow(&self) -> &[T] {
+                self.as_slice()
+            }
+        }
+
+        impl<T, $($generics)*> core::borrow::BorrowMut<[T]> for $ty {
+            fn borrow_mut(&mut self) -> &mut [T] {
+                self.as_mut_slice()
+            }
+        }
+
+        impl<T, U, $($generics)*> PartialEq<[U]> for $ty
+        where
+            T: PartialEq<U>,
+        {
+            fn eq(&self, other: &[U]) -> bool {
+                self.as_slice() == other
+            }
+        }
+
+        impl<T, U, $($generics)*> PartialEq<&[U]> for $ty
+        where
+            T: PartialEq<U>,
+        {
+            fn eq(&self, other: &&[U]) -> bool {
+                self.as_slice() == *other
+            }
+        }
+
+        impl<T, U, $($generics)*, const M: usize> PartialEq<[U; M]> for $ty
+        where
+            T: PartialEq<U>,
+        {
+            fn eq(&self, other: &[U; M]) -> bool {
+                self.as_slice() == other
+            }
+        }
+
+        impl<T, $($generics)*> Eq for $ty where T: Eq {}
+
+        impl<T, $($generics)*> PartialOrd for $ty
+        where
+            T: PartialOrd,
+        {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                self.as_slice().partial_cmp(other.as_slice())
+            }
+        }
+
+        impl<T, $($generics)*> Ord for $ty
+        where
+            T: Ord,
+        {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.as_slice().cmp(other.as_slice())
+            }
+        }
+
+        impl<T, $($generics)*> core::hash::Hash for $ty
+        where
+            T: core::hash::Hash,
+        {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.as_slice().hash(state);
+            }
+        }
+    };
+}
+
+pub(crate) use impl_slice_traits;
@@ -0,0 +1,535 @@
+// Inline-then-heap vector, only built with the `alloc` cargo feature.
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::RangeBounds;
+
+use crate::{ArrayVec, ArrayVecIntoIter, Drain};
+
+// Same API shape as ArrayVec, but pushing past `N` moves everything to a
+// `Vec` instead of failing. It never moves back on its own; call
+// `shrink_to_fit` for that.
+pub struct SmallVec<T, const N: usize> {
+    data: Data<T, N>,
+}
+
+enum Data<T, const N: usize> {
+    Inline(ArrayVec<T, N>),
+    Heap(Vec<T>),
+}
+
+impl<T, const N: usize> SmallVec<T, N> {
+    /// Creates a new empty SmallVec, stored inline.
+    pub const fn new() -> Self {
+        SmallVec {
+            data: Data::Inline(ArrayVec::new()),
+        }
+    }
+
+    /// Returns `true` if the elements have moved to the heap.
+    pub const fn spilled(&self) -> bool {
+        matches!(self.data, Data::Heap(_))
+    }
+
+    /// Returns the number of elements.
+    pub const fn len(&self) -> usize {
+        match &self.data {
+            Data::Inline(vec) => vec.len(),
+            Data::Heap(vec) => vec.len(),
+        }
+    }
+
+    /// Returns `true` if there are no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many elements fit before the next (re)allocation:
+    /// `N` while inline, the `Vec`'s capacity once spilled.
+    pub fn capacity(&self) -> usize {
+        match &self.data {
+            Data::Inline(_) => N,
+            Data::Heap(vec) => vec.capacity(),
+        }
+    }
+
+    /// Returns the elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        match &self.data {
+            Data::Inline(vec) => vec.as_slice(),
+            Data::Heap(vec) => vec.as_slice(),
+        }
+    }
+
+    /// Returns the elements as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.data {
+            Data::Inline(vec) => vec.as_mut_slice(),
+            Data::Heap(vec) => vec.as_mut_slice(),
+        }
+    }
+
+    // Moves inline elements to a Vec with room for `additional` more
+    // (at least double `N`, so repeated pushes don't reallocate at once)
+    // and returns it. Already-spilled storage is returned as is.
+    fn spill(&mut self, additional: usize) -> &mut Vec<T> {
+        if let Data::Inline(inline) = &mut self.data {
+            let mut vec = Vec::with_capacity((inline.len() + additional).max(N * 2));
+            vec.extend(inline.drain(..));
+            self.data = Data::Heap(vec);
+        }
+        match &mut self.data {
+            Data::Heap(vec) => vec,
+            Data::Inline(_) => unreachable!(),
+        }
+    }
+
+    /// Appends an element, spilling to the heap if the inline storage
+    /// is full.
+    pub fn push(&mut self, value: T) {
+        match &mut self.data {
+            Data::Inline(vec) => {
+                if let Err(err) = vec.try_push(value) {
+                    self.spill(1).push(err.element());
+                }
+            }
+            Data::Heap(vec) => vec.push(value),
+        }
+    }
+
+    /// Removes the last element and returns it, or `None` if empty.
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.data {
+            Data::Inline(vec) => vec.pop(),
+            Data::Heap(vec) => vec.pop(),
+        }
+    }
+
+    /// Inserts at `index`, shifting later elements right. Spills if the
+    /// inline storage is full. Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        match &mut self.data {
+            Data::Inline(vec) => {
+                if let Err(err) = vec.try_insert(index, value) {
+                    self.spill(1).insert(index, err.element());
+                }
+            }
+            Data::Heap(vec) => vec.insert(index, value),
+        }
+    }
+
+    /// Removes and returns the element at `index`, shifting later ones
+    /// left. Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        match &mut self.data {
+            Data::Inline(vec) => vec.remove(index),
+            Data::Heap(vec) => vec.remove(index),
+        }
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the
+    /// last one. Panics if `index >= len`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        match &mut self.data {
+            Data::Inline(vec) => vec.swap_remove(index),
+            Data::Heap(vec) => vec.swap_remove(index),
+        }
+    }
+
+    /// Shortens to `len` elements, dropping the rest. No-op if `len` is
+    /// not less than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        match &mut self.data {
+            Data::Inline(vec) => vec.truncate(len),
+            Data::Heap(vec) => vec.truncate(len),
+        }
+    }
+
+    /// Drops all elements. Keeps the heap buffer if spilled.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, in order.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        match &mut self.data {
+            Data::Inline(vec) => vec.retain(f),
+            Data::Heap(vec) => vec.retain(f),
+        }
+    }
+
+    /// Like `retain`, but `f` gets mutable access to each element.
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        match &mut self.data {
+            Data::Inline(vec) => vec.retain_mut(f),
+            Data::Heap(vec) => vec.retain_mut(f),
+        }
+    }
+
+    /// Removes consecutive elements for which `same_bucket(current,
+    /// previous)` returns `true`, keeping the first of each run.
+    pub fn dedup_by<F>(&mut self, same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        match &mut self.data {
+            Data::Inline(vec) => vec.dedup_by(same_bucket),
+            Data::Heap(vec) => vec.dedup_by(same_bucket),
+        }
+    }
+
+    /// Removes consecutive elements that resolve to the same key.
+    pub fn dedup_by_key<K, F>(&mut self, key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        match &mut self.data {
+            Data::Inline(vec) => vec.dedup_by_key(key),
+            Data::Heap(vec) => vec.dedup_by_key(key),
+        }
+    }
+
+    /// Removes the elements in `range` and returns them as a
+    /// double-ended iterator; whatever isn't consumed is dropped with
+    /// it. Stays on the heap if spilled. Panics if the range start is
+    /// after its end, or the end is past `len`.
+    pub fn drain<R>(&mut self, range: R) -> SmallVecDrain<'_, T, N>
+    where
+        R: RangeBounds<usize>,
+    {
+        let inner = match &mut self.data {
+            Data::Inline(vec) => DrainInner::Inline(vec.drain(range)),
+            Data::Heap(vec) => DrainInner::Heap(vec.drain(range)),
+        };
+        SmallVecDrain { inner }
+    }
+
+    /// Moves back inline if the elements fit in `N`, freeing the heap
+    /// buffer; otherwise shrinks the `Vec`.
+    pub fn shrink_to_fit(&mut self) {
+        if let Data::Heap(vec) = &mut self.data {
+            if vec.len() <= N {
+                // Can't overflow: checked above.
+                let inline = vec.drain(..).collect();
+                self.data = Data::Inline(inline);
+            } else {
+                vec.shrink_to_fit();
+            }
+        }
+    }
+
+    /// Converts into a `Vec`, allocating only if still inline.
+    pub fn into_vec(self) -> Vec<T> {
+        match self.data {
+            Data::Inline(vec) => vec.into(),
+            Data::Heap(vec) => vec,
+        }
+    }
+
+    /// Converts into an ArrayVec if the elements fit in `N`, handing
+    /// `self` back otherwise.
+    pub fn into_array_vec(self) -> Result<ArrayVec<T, N>, Self> {
+        match self.data {
+            Data::Inline(vec) => Ok(vec),
+            Data::Heap(vec) if vec.len() <= N => Ok(vec.into_iter().collect()),
+            Data::Heap(vec) => Err(SmallVec {
+                data: Data::Heap(vec),
+            }),
+        }
+    }
+
+    /// Returns an iterator over references.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns an iterator over mutable references.
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl<T: Clone, const N: usize> SmallVec<T, N> {
+    /// Clones and appends all of `other`, spilling once up front if it
+    /// doesn't fit inline.
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        match &mut self.data {
+            Data::Inline(vec) => {
+                if vec.try_extend_from_slice(other).is_err() {
+                    self.spill(other.len()).extend_from_slice(other);
+                }
+            }
+            Data::Heap(vec) => vec.extend_from_slice(other),
+        }
+    }
+}
+
+impl<T: PartialEq, const N: usize> SmallVec<T, N> {
+    /// Removes consecutive repeated elements, keeping the first of each run.
+    pub fn dedup(&mut self) {
+        match &mut self.data {
+            Data::Inline(vec) => vec.dedup(),
+            Data::Heap(vec) => vec.dedup(),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for SmallVec<T, N> {
+    fn clone(&self) -> Self {
+        let data = match &self.data {
+            Data::Inline(vec) => Data::Inline(vec.clone()),
+            Data::Heap(vec) => Data::Heap(vec.clone()),
+        };
+        SmallVec { data }
+    }
+}
+
+// Slice methods (sort, binary_search, indexing, ...) come from Deref,
+// and comparisons, hashing and Debug go by the slice too, as for
+// ArrayVec, so they never depend on where the elements are stored.
+crate::slice_traits::impl_slice_traits!([const N: usize] SmallVec<T, N>);
+
+impl<T, U, const N: usize, const M: usize> PartialEq<SmallVec<U, M>> for SmallVec<T, N>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &SmallVec<U, M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T, const N: usize> From<ArrayVec<T, N>> for SmallVec<T, N> {
+    fn from(vec: ArrayVec<T, N>) -> Self {
+        SmallVec {
+            data: Data::Inline(vec),
+        }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for SmallVec<T, N> {
+    fn from(array: [T; N]) -> Self {
+        ArrayVec::from(array).into()
+    }
+}
+
+// Keeps the allocation as is, even if the elements would fit inline.
+impl<T, const N: usize> From<Vec<T>> for SmallVec<T, N> {
+    fn from(vec: Vec<T>) -> Self {
+        SmallVec {
+            data: Data::Heap(vec),
+        }
+    }
+}
+
+impl<T, const N: usize> From<SmallVec<T, N>> for Vec<T> {
+    fn from(vec: SmallVec<T, N>) -> Self {
+        vec.into_vec()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        match &mut self.data {
+            Data::Inline(vec) => {
+                // Fill inline first; whatever doesn't fit goes to the heap.
+                if let Err(err) = vec.try_extend(iter) {
+                    let rest = err.element();
+                    self.spill(rest.size_hint().0).extend(rest);
+                }
+            }
+            Data::Heap(vec) => vec.extend(iter),
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = SmallVec::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+// Owning iterator, reusing ArrayVec's or Vec's depending on where the
+// elements were.
+pub struct SmallVecIntoIter<T, const N: usize> {
+    inner: IntoIterInner<T, N>,
+}
+
+enum IntoIterInner<T, const N: usize> {
+    Inline(ArrayVecIntoIter<T, N>),
+    Heap(alloc::vec::IntoIter<T>),
+}
+
+impl<T, const N: usize> SmallVecIntoIter<T, N> {
+    /// Returns the remaining elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        match &self.inner {
+            IntoIterInner::Inline(iter) => iter.as_slice(),
+            IntoIterInner::Heap(iter) => iter.as_slice(),
+        }
+    }
+
+    /// Returns the remaining elements as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.inner {
+            IntoIterInner::Inline(iter) => iter.as_mut_slice(),
+            IntoIterInner::Heap(iter) => iter.as_mut_slice(),
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for SmallVecIntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match &mut self.inner {
+            IntoIterInner::Inline(iter) => iter.next(),
+            IntoIterInner::Heap(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.as_slice().len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for SmallVecIntoIter<T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        match &mut self.inner {
+            IntoIterInner::Inline(iter) => iter.next_back(),
+            IntoIterInner::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for SmallVecIntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for SmallVecIntoIter<T, N> {}
+
+impl<T: Clone, const N: usize> Clone for SmallVecIntoIter<T, N> {
+    fn clone(&self) -> Self {
+        let inner = match &self.inner {
+            IntoIterInner::Inline(iter) => IntoIterInner::Inline(iter.clone()),
+            IntoIterInner::Heap(iter) => IntoIterInner::Heap(iter.clone()),
+        };
+        SmallVecIntoIter { inner }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVecIntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SmallVecIntoIter")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+// Draining iterator, from `SmallVec::drain`. Wraps ArrayVec's or Vec's
+// Drain depending on where the elements are.
+pub struct SmallVecDrain<'a, T, const N: usize> {
+    inner: DrainInner<'a, T, N>,
+}
+
+enum DrainInner<'a, T, const N: usize> {
+    Inline(Drain<'a, T, N>),
+    Heap(alloc::vec::Drain<'a, T>),
+}
+
+impl<T, const N: usize> SmallVecDrain<'_, T, N> {
+    /// Returns the elements not yet yielded as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        match &self.inner {
+            DrainInner::Inline(iter) => iter.as_slice(),
+            DrainInner::Heap(iter) => iter.as_slice(),
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for SmallVecDrain<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match &mut self.inner {
+            DrainInner::Inline(iter) => iter.next(),
+            DrainInner::Heap(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.as_slice().len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for SmallVecDrain<'_, T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        match &mut self.inner {
+            DrainInner::Inline(iter) => iter.next_back(),
+            DrainInner::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for SmallVecDrain<'_, T, N> {}
+
+impl<T, const N: usize> FusedIterator for SmallVecDrain<'_, T, N> {}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVecDrain<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SmallVecDrain")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for SmallVec<T, N> {
+    type Item = T;
+    type IntoIter = SmallVecIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let inner = match self.data {
+            Data::Inline(vec) => IntoIterInner::Inline(vec.into_iter()),
+            Data::Heap(vec) => IntoIterInner::Heap(vec.into_iter()),
+        };
+        SmallVecIntoIter { inner }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,376 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Bound, Deref, RangeBounds};
+
+use crate::{ArrayVec, ArrayVecIntoIter, CapacityError};
+
+// Ordering used by a SortedArrayVec. Implemented by the `Ascending` and
+// `Descending` markers and by any `Fn(&T, &T) -> Ordering` closure.
+pub trait Comparator<T> {
+    /// Compares two elements; `Less` means `a` sorts before `b`.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+// Sorts by `Ord`, smallest first. The default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ascending;
+
+// Sorts by `Ord`, largest first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Descending;
+
+impl<T: Ord> Comparator<T> for Ascending {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T: Ord> Comparator<T> for Descending {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T, F> Comparator<T> for F
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+// ArrayVec whose init prefix is always sorted by `C`. Inserts find their
+// slot by binary search and shift the tail, so they're O(log N) compares
+// plus an O(N) move. Equal elements keep insertion order.
+//
+// Only shared access to the elements is handed out (`Deref` to a slice,
+// no `DerefMut`), so the order can't be broken from outside.
+pub struct SortedArrayVec<T, const N: usize, C = Ascending> {
+    data: ArrayVec<T, N>,
+    cmp: C,
+}
+
+impl<T, const N: usize, C> SortedArrayVec<T, N, C>
+where
+    C: Comparator<T> + Default,
+{
+    /// Creates a new empty SortedArrayVec.
+    pub fn new() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, const N: usize, C> SortedArrayVec<T, N, C>
+where
+    C: Comparator<T>,
+{
+    /// Creates a new empty SortedArrayVec ordered by `cmp`, e.g. a
+    /// closure comparing readings by timestamp.
+    pub const fn with_comparator(cmp: C) -> Self {
+        SortedArrayVec {
+            data: ArrayVec::new(),
+            cmp,
+        }
+    }
+
+    /// Sorts an existing ArrayVec by `cmp` (unstable, no allocation).
+    pub fn from_array_vec(mut data: ArrayVec<T, N>, cmp: C) -> Self {
+        data.sort_unstable_by(|a, b| cmp.compare(a, b));
+        SortedArrayVec { data, cmp }
+    }
+
+    /// Returns the number of elements.
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if there are no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns `true` if no more elements fit.
+    pub const fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the elements as a sorted slice.
+    pub const fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Returns the comparator.
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    // First index whose element sorts after `value` (upper bound), so
+    // inserting there keeps equal elements in insertion order.
+    fn insert_index(&self, value: &T) -> usize {
+        self.data
+            .partition_point(|x| self.cmp.compare(x, value) != Ordering::Greater)
+    }
+
+    /// Inserts `value` in sorted position and returns its index. Hands
+    /// `value` back in a `CapacityError` if full.
+    pub fn try_insert(&mut self, value: T) -> Result<usize, CapacityError<T>> {
+        if self.data.is_full() {
+            return Err(CapacityError::new(value));
+        }
+        let index = self.insert_index(&value);
+        // Can't fail: not full.
+        let _ = self.data.try_insert(index, value);
+        Ok(index)
+    }
+
+    /// Top-K insert that drops from the back: if full, keeps `value`
+    /// only if it sorts before the last element, evicting that one.
+    /// Returns whichever element didn't make the cut, if any.
+    /// With `Ascending`, this keeps the `N` smallest values seen.
+    pub fn insert_evicting_last(&mut self, value: T) -> Option<T> {
+        if !self.data.is_full() {
+            let _ = self.try_insert(value);
+            return None;
+        }
+        match self.data.last() {
+            Some(last) if self.cmp.compare(&value, last) == Ordering::Less => {
+                let evicted = self.data.pop();
+                let _ = self.try_insert(value);
+                evicted
+            }
+            // Not better than the worst kept (or `N == 0`).
+            _ => Some(value),
+        }
+    }
+
+    /// Top-K insert that drops from the front: if full, keeps `value`
+    /// only if it sorts after the first element, evicting that one.
+    /// Returns whichever element didn't make the cut, if any.
+    /// With `Ascending`, this keeps the `N` largest values seen.
+    pub fn insert_evicting_first(&mut self, value: T) -> Option<T> {
+        if !self.data.is_full() {
+            let _ = self.try_insert(value);
+            return None;
+        }
+        match self.data.first() {
+            Some(first) if self.cmp.compare(&value, first) == Ordering::Greater => {
+                let evicted = self.data.remove(0);
+                let _ = self.try_insert(value);
+                Some(evicted)
+            }
+            _ => Some(value),
+        }
+    }
+
+    /// Binary searches for `value` by the comparator (not `Ord`, unlike
+    /// the slice method it shadows). `Ok` holds the index of a match,
+    /// `Err` the index where it would be inserted.
+    pub fn binary_search(&self, value: &T) -> Result<usize, usize> {
+        self.data.binary_search_by(|x| self.cmp.compare(x, value))
+    }
+
+    /// Returns `true` if an element compares equal to `value`.
+    pub fn contains(&self, value: &T) -> bool {
+        self.binary_search(value).is_ok()
+    }
+
+    /// Returns the elements within `range`, as bounds compared by the
+    /// comparator. An empty slice if nothing falls in it. Bounds follow
+    /// the sort order, so with `Descending` the start is the larger one.
+    pub fn range<R>(&self, range: R) -> &[T]
+    where
+        R: RangeBounds<T>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(b) => self
+                .data
+                .partition_point(|x| self.cmp.compare(x, b) == Ordering::Less),
+            Bound::Excluded(b) => self
+                .data
+                .partition_point(|x| self.cmp.compare(x, b) != Ordering::Greater),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(b) => self
+                .data
+                .partition_point(|x| self.cmp.compare(x, b) != Ordering::Greater),
+            Bound::Excluded(b) => self
+                .data
+                .partition_point(|x| self.cmp.compare(x, b) == Ordering::Less),
+            Bound::Unbounded => self.data.len(),
+        };
+        // An inverted range (start sorting after end) is just empty.
+        &self.data[start..end.max(start)]
+    }
+
+    /// Removes and returns the element at `index`. Panics if
+    /// `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.data.remove(index)
+    }
+
+    /// Removes one element comparing equal to `value`, if any.
+    pub fn remove_value(&mut self, value: &T) -> Option<T> {
+        let index = self.binary_search(value).ok()?;
+        Some(self.data.remove(index))
+    }
+
+    /// Removes and returns the last (greatest by the comparator) element.
+    pub fn pop_last(&mut self) -> Option<T> {
+        self.data.pop()
+    }
+
+    /// Removes and returns the first (least by the comparator) element.
+    pub fn pop_first(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(self.data.remove(0))
+    }
+
+    /// Keeps only the elements for which `f` returns `true`. Order is
+    /// preserved, so the result stays sorted.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.data.retain(f);
+    }
+
+    /// Shortens to the first `len` elements, dropping the rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.data.truncate(len);
+    }
+
+    /// Drops all elements.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Merges `other` in one pass (both are already sorted the same
+    /// way, having the same comparator type), using this vector's
+    /// comparator. The first `N` elements in merged order are kept; the
+    /// ones past capacity come back, still sorted. There can be at most
+    /// `M` of them. Ties put `self`'s elements first.
+    pub fn merge<const M: usize>(self, other: SortedArrayVec<T, M, C>) -> (Self, ArrayVec<T, M>) {
+        let SortedArrayVec { data, cmp } = self;
+        let mut left = data.into_iter().peekable();
+        let mut right = other.data.into_iter().peekable();
+        let mut merged = ArrayVec::new();
+        let mut overflow = ArrayVec::new();
+
+        loop {
+            let take_left = match (left.peek(), right.peek()) {
+                (Some(a), Some(b)) => cmp.compare(b, a) != Ordering::Less,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            let next = if take_left { left.next() } else { right.next() };
+            let Some(value) = next else { break };
+            if let Err(err) = merged.try_push(value) {
+                // Can't fail: `self` had at most `N`, so at most `M`
+                // end up here.
+                let _ = overflow.try_push(err.element());
+            }
+        }
+        (SortedArrayVec { data: merged, cmp }, overflow)
+    }
+
+    /// Returns an iterator over the elements in sorted order.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Unwraps into the underlying (sorted) ArrayVec.
+    pub fn into_array_vec(self) -> ArrayVec<T, N> {
+        self.data
+    }
+}
+
+impl<T, const N: usize, C> Default for SortedArrayVec<T, N, C>
+where
+    C: Comparator<T> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, C> Clone for SortedArrayVec<T, N, C>
+where
+    T: Clone,
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        SortedArrayVec {
+            data: self.data.clone(),
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<T, const N: usize, C> Deref for SortedArrayVec<T, N, C> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.data.as_slice()
+    }
+}
+
+impl<T, const N: usize, C> AsRef<[T]> for SortedArrayVec<T, N, C> {
+    fn as_ref(&self) -> &[T] {
+        self.data.as_slice()
+    }
+}
+
+// Compares contents only; the comparators may differ.
+impl<T, U, const N: usize, const M: usize, C, C2> PartialEq<SortedArrayVec<U, M, C2>>
+    for SortedArrayVec<T, N, C>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &SortedArrayVec<U, M, C2>) -> bool {
+        self.data.as_slice() == other.data.as_slice()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize, C> fmt::Debug for SortedArrayVec<T, N, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+// Sorts by `Ord` (or whatever default comparator `C` is).
+impl<T, const N: usize, C> From<ArrayVec<T, N>> for SortedArrayVec<T, N, C>
+where
+    C: Comparator<T> + Default,
+{
+    fn from(data: ArrayVec<T, N>) -> Self {
+        Self::from_array_vec(data, C::default())
+    }
+}
+
+impl<'a, T, const N: usize, C> IntoIterator for &'a SortedArrayVec<T, N, C> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<T, const N: usize, C> IntoIterator for SortedArrayVec<T, N, C> {
+    type Item = T;
+    type IntoIter = ArrayVecIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
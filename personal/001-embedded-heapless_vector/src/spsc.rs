@@ -0,0 +1,265 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::CapacityError;
+
+// Lock-free single-producer single-consumer queue on inline
+// `MaybeUninit` storage, for handing data from an interrupt handler to
+// the main loop (or between two threads) without a critical section.
+//
+// `head` (next slot to read) is only written by the consumer and `tail`
+// (next slot to write) only by the producer. Both count in `0..2N`
+// rather than `0..N`, so `head == tail` means empty and a distance of
+// `N` means full, and all `N` slots are usable. Only atomic loads and
+// stores are used, so it also works on cores without compare-and-swap
+// (e.g. Cortex-M0).
+//
+// Invariant: the `len` slots from `head` (mod N) are init.
+pub struct SpscQueue<T, const N: usize> {
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    // Set by `try_split`, so a `static` queue is only split once.
+    split: AtomicBool,
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+}
+
+// Producer half: the only one allowed to enqueue.
+pub struct Producer<'a, T, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+}
+
+// Consumer half: the only one allowed to dequeue.
+pub struct Consumer<'a, T, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+}
+
+// SAFETY: Each half touches disjoint state (see the struct comment), and
+// `split` borrows the queue mutably (`try_split` hands them out once),
+// so there's only ever one of each. Values cross from one context to
+// the other, hence `T: Send`.
+unsafe impl<T: Send, const N: usize> Send for Producer<'_, T, N> {}
+unsafe impl<T: Send, const N: usize> Send for Consumer<'_, T, N> {}
+
+// SAFETY: Through `&self` only atomic loads happen (`len` and friends)
+// plus the `try_split` flag; the slots are only reached through the
+// halves or `&mut self`. This is what lets the queue be a `static`.
+unsafe impl<T: Send, const N: usize> Sync for SpscQueue<T, N> {}
+
+impl<T, const N: usize> SpscQueue<T, N> {
+    // `2 * N` must not overflow the index space.
+    const FITS: () = assert!(N <= usize::MAX / 2, "SpscQueue capacity is too large");
+
+    /// Creates a new empty queue. `const`, so it can live in a `static`
+    /// and be split there with `try_split`.
+    pub const fn new() -> Self {
+        let () = Self::FITS;
+        SpscQueue {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            split: AtomicBool::new(false),
+            buffer: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+        }
+    }
+
+    /// Splits the queue into its producer and consumer halves, which can
+    /// be moved to different contexts (e.g. an ISR and the main loop).
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        (Producer { queue: self }, Consumer { queue: self })
+    }
+
+    /// Splits a `static` queue. Only the first call gets the halves;
+    /// later ones return `None`, as the halves may still be in use.
+    ///
+    /// Needs an atomic swap, so it's missing on cores without
+    /// compare-and-swap (e.g. Cortex-M0); split a local queue there.
+    #[cfg(target_has_atomic = "8")]
+    pub fn try_split(&'static self) -> Option<(Producer<'static, T, N>, Consumer<'static, T, N>)> {
+        if self.split.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        Some((Producer { queue: self }, Consumer { queue: self }))
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of queued elements. Only a snapshot while the
+    /// queue is split.
+    pub fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        Self::distance(head, tail)
+    }
+
+    /// Returns `true` if nothing is queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if no more elements fit.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Enqueues without splitting (needs `&mut`, so no other half is
+    /// active).
+    pub fn try_enqueue(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        // SAFETY: `&mut self` means we're the only producer.
+        unsafe { self.enqueue_inner(value) }
+    }
+
+    /// Dequeues without splitting.
+    pub fn dequeue(&mut self) -> Option<T> {
+        // SAFETY: `&mut self` means we're the only consumer.
+        unsafe { self.dequeue_inner() }
+    }
+
+    // Next index in `0..2N`.
+    fn next(i: usize) -> usize {
+        if i + 1 == 2 * N { 0 } else { i + 1 }
+    }
+
+    // Number of elements from `head` to `tail` in `0..2N` index space.
+    fn distance(head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            2 * N - head + tail
+        }
+    }
+
+    // Slot in `buffer` for an index in `0..2N`.
+    fn slot(&self, i: usize) -> &UnsafeCell<MaybeUninit<T>> {
+        &self.buffer[if i >= N { i - N } else { i }]
+    }
+
+    // SAFETY: Caller must be the only producer.
+    unsafe fn enqueue_inner(&self, value: T) -> Result<(), CapacityError<T>> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        // Acquire: the consumer must be done reading a slot before we
+        // reuse it.
+        let head = self.head.load(Ordering::Acquire);
+        if Self::distance(head, tail) == N {
+            return Err(CapacityError::new(value));
+        }
+        // SAFETY: Not full, so slot `tail` is uninit and the consumer
+        // won't touch it until the Release store below publishes it.
+        unsafe { (*self.slot(tail).get()).write(value) };
+        self.tail.store(Self::next(tail), Ordering::Release);
+        Ok(())
+    }
+
+    // SAFETY: Caller must be the only consumer.
+    unsafe fn dequeue_inner(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        // Acquire: pairs with the producer's Release, so the write to
+        // the slot is visible.
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        // SAFETY: Not empty, so slot `head` is init and owned by us
+        // until we move `head` past it.
+        let value = unsafe { (*self.slot(head).get()).assume_init_read() };
+        self.head.store(Self::next(head), Ordering::Release);
+        Some(value)
+    }
+
+    // SAFETY: Caller must be the only consumer; the reference must not
+    // outlive the next dequeue.
+    unsafe fn peek_inner(&self) -> Option<&T> {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        Some(unsafe { (*self.slot(head).get()).assume_init_ref() })
+    }
+}
+
+impl<T, const N: usize> Producer<'_, T, N> {
+    /// Enqueues a value if there's space, handing it back in a
+    /// `CapacityError` if full.
+    pub fn try_enqueue(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        // SAFETY: There's only one Producer per split.
+        unsafe { self.queue.enqueue_inner(value) }
+    }
+
+    /// Returns the number of queued elements (a snapshot).
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if nothing is queued (a snapshot).
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns `true` if no more elements fit. Can only become `false`
+    /// behind our back, never `true`.
+    pub fn is_full(&self) -> bool {
+        self.queue.is_full()
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> Consumer<'_, T, N> {
+    /// Dequeues the oldest value, if any.
+    pub fn dequeue(&mut self) -> Option<T> {
+        // SAFETY: There's only one Consumer per split.
+        unsafe { self.queue.dequeue_inner() }
+    }
+
+    /// Returns the oldest value without dequeuing it.
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: Only one Consumer, and `dequeue` needs `&mut self`, so
+        // the value can't be moved out while this borrow lives.
+        unsafe { self.queue.peek_inner() }
+    }
+
+    /// Returns the number of queued elements (a snapshot).
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if nothing is queued. Can only become `false`
+    /// behind our back, never `true`.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns the capacity (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> Default for SpscQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Drop whatever is still queued. `&mut self` means both halves are gone.
+impl<T, const N: usize> Drop for SpscQueue<T, N> {
+    fn drop(&mut self) {
+        while self.dequeue().is_some() {}
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for SpscQueue<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscQueue")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish()
+    }
+}
@@ -0,0 +1,31 @@
+// Host-side extras, only built with the `std` cargo feature. The error
+// types need nothing here: `core::error::Error` is `std::error::Error`.
+use std::io;
+
+use crate::{ArrayVec, LenType};
+
+// Writes as many bytes as fit, like `io::Write for &mut [u8]`. Once full,
+// `write` returns `Ok(0)`, so `write_all` fails with `WriteZero`.
+impl<const N: usize, L: LenType> io::Write for ArrayVec<u8, N, L> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.remaining_capacity());
+        // Can't fail: `n` fits.
+        let _ = self.try_extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Reads consume bytes from the front, shifting the rest down.
+impl<const N: usize, L: LenType> io::Read for ArrayVec<u8, N, L> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.len());
+        for (dst, src) in buf.iter_mut().zip(self.drain(..n)) {
+            *dst = src;
+        }
+        Ok(n)
+    }
+}
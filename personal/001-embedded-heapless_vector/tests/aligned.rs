@@ -0,0 +1,79 @@
+// AlignedArrayVec layout: the element storage must start on `A`'s
+// boundary for every marker and length type, also as an array element,
+// and the ArrayVec API must work through Deref.
+use std::mem::align_of;
+
+use heapless_vector::{
+    Align8, Align16, Align32, Align64, Align128, AlignedArrayVec, Alignment, ArrayVec, LenType,
+};
+
+fn check<A: Alignment, L: LenType>(align: usize) {
+    assert_eq!(align_of::<A>(), align);
+    assert!(align_of::<AlignedArrayVec<u8, 3, A, L>>() >= align);
+
+    let vec = AlignedArrayVec::<u8, 3, A, L>::new();
+    assert_eq!(vec.as_ptr() as usize % align, 0);
+
+    // Odd sizes, so a second array element would land off-boundary if
+    // the struct's size weren't padded to its alignment.
+    let array = [
+        AlignedArrayVec::<u8, 3, A, L>::new(),
+        AlignedArrayVec::<u8, 3, A, L>::new(),
+    ];
+    for vec in &array {
+        assert_eq!(vec.as_ptr() as usize % align, 0);
+    }
+
+    // Boxed too: the allocation has to honour the alignment.
+    let boxed = Box::new(AlignedArrayVec::<u16, 5, A, L>::new());
+    assert_eq!(boxed.as_ptr() as usize % align, 0);
+
+    let mut vec = AlignedArrayVec::<u32, 4, A, L>::new();
+    vec.try_push(1).unwrap();
+    vec.try_push(2).unwrap();
+    vec.try_push(3).unwrap();
+    assert_eq!(vec.as_ptr() as usize % align, 0);
+    assert_eq!(vec.as_slice().as_ptr(), vec.as_ptr());
+    assert_eq!(vec.pop(), Some(3));
+    vec[0] = 10;
+    assert_eq!(vec[..], [10, 2]);
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec.capacity(), 4);
+
+    // Swapping the inner ArrayVec keeps the address.
+    let before = vec.as_ptr();
+    *vec = ArrayVec::from([7, 8, 9, 10]);
+    assert_eq!(vec.as_ptr(), before);
+    assert!(vec.is_full());
+    assert_eq!(vec.try_push(11).unwrap_err().element(), 11);
+    assert_eq!(vec.into_inner(), [7, 8, 9, 10]);
+}
+
+fn check_all<L: LenType>() {
+    check::<Align8, L>(8);
+    check::<Align16, L>(16);
+    check::<Align32, L>(32);
+    check::<Align64, L>(64);
+    check::<Align128, L>(128);
+}
+
+#[test]
+fn storage_aligned_with_usize_len() {
+    check_all::<usize>();
+}
+
+#[test]
+fn storage_aligned_with_u8_len() {
+    check_all::<u8>();
+}
+
+#[test]
+fn collect_and_clone_stay_aligned() {
+    let vec: AlignedArrayVec<u8, 7, Align64, u8> = (1..=5).collect();
+    let cloned = vec.clone();
+    for vec in [&vec, &cloned] {
+        assert_eq!(vec.as_ptr() as usize % 64, 0);
+        assert_eq!(vec[..], [1, 2, 3, 4, 5]);
+    }
+    assert_eq!(format!("{vec:?}"), "[1, 2, 3, 4, 5]");
+}
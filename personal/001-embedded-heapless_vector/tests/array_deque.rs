@@ -0,0 +1,78 @@
+// ArrayDeque ring-buffer behaviour: wraparound of the head index from
+// both ends, and what the overwrite mode hands back.
+use std::collections::VecDeque;
+
+use heapless_vector::{ArrayDeque, CapacityError};
+
+fn contents<const N: usize>(deque: &ArrayDeque<u32, N>) -> Vec<u32> {
+    deque.iter().copied().collect()
+}
+
+#[test]
+fn wraparound_after_mixed_push_front_and_pop_back() {
+    let mut deque = ArrayDeque::<u32, 4>::new();
+    // Pushing to the front from an empty deque wraps `head` to the end
+    // of the storage straight away.
+    deque.try_push_front(1).unwrap();
+    deque.try_push_front(0).unwrap();
+    deque.try_push_back(2).unwrap();
+    assert_eq!(contents(&deque), [0, 1, 2]);
+    let (front, back) = deque.as_slices();
+    assert_eq!((front, back), (&[0, 1][..], &[2][..]));
+
+    // Keep rotating: each round pops the back and pushes a new front,
+    // so the init run walks backwards around the buffer several times.
+    // VecDeque is the reference.
+    let mut model = VecDeque::from([0, 1, 2]);
+    for next in 3..15 {
+        assert_eq!(deque.pop_back(), model.pop_back());
+        deque.try_push_front(next).unwrap();
+        model.push_front(next);
+        assert_eq!(contents(&deque), Vec::from(model.clone()));
+        assert!(deque.iter().rev().eq(model.iter().rev()));
+        assert_eq!(deque.front(), model.front());
+        assert_eq!(deque.back(), model.back());
+        let (front, back) = deque.as_slices();
+        assert_eq!(front.len() + back.len(), 3);
+    }
+    assert_eq!(contents(&deque), [14, 13, 12]);
+
+    deque.try_push_front(99).unwrap();
+    assert!(deque.is_full());
+    assert_eq!(deque.try_push_front(100), Err(CapacityError::new(100)));
+    assert_eq!(deque.try_push_back(101), Err(CapacityError::new(101)));
+    assert_eq!(deque.get(0), Some(&99));
+    assert_eq!(deque.get(3), Some(&12));
+    assert_eq!(deque.get(4), None);
+
+    for value in deque.iter_mut() {
+        *value += 1;
+    }
+    assert_eq!(deque.into_iter().collect::<Vec<_>>(), [100, 15, 14, 13]);
+}
+
+#[test]
+fn overwrite_mode_returns_evicted_front() {
+    let mut log = ArrayDeque::<u32, 3>::new();
+    assert_eq!(log.push_back_overwrite(1), None);
+    assert_eq!(log.push_back_overwrite(2), None);
+    assert_eq!(log.push_back_overwrite(3), None);
+
+    // Full: each push evicts and returns the oldest.
+    assert_eq!(log.push_back_overwrite(4), Some(1));
+    assert_eq!(log.push_back_overwrite(5), Some(2));
+    assert_eq!(contents(&log), [3, 4, 5]);
+
+    // After making room there's nothing to evict again.
+    assert_eq!(log.pop_front(), Some(3));
+    assert_eq!(log.push_back_overwrite(6), None);
+    assert_eq!(log.push_back_overwrite(7), Some(4));
+    assert_eq!(contents(&log), [5, 6, 7]);
+}
+
+#[test]
+fn overwrite_mode_with_zero_capacity_hands_value_back() {
+    let mut log = ArrayDeque::<u32, 0>::new();
+    assert_eq!(log.push_back_overwrite(1), Some(1));
+    assert!(log.is_empty());
+}
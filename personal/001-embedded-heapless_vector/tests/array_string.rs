@@ -0,0 +1,116 @@
+// ArrayString stays valid UTF-8 and all-or-nothing on overflow: char
+// boundaries, multi-byte pops, partial `write!`s, and the value handed
+// back on failure. Also usable as a map key.
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+
+use heapless_vector::{ArrayString, IndexMap, IndexSet};
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+#[should_panic(expected = "char boundary")]
+fn truncate_inside_a_char_panics() {
+    let mut s = ArrayString::<8>::try_from("aé").unwrap();
+    // 'é' is bytes 1..3.
+    s.truncate(2);
+}
+
+#[test]
+fn truncate_on_boundaries() {
+    let mut s = ArrayString::<16>::try_from("aé€").unwrap();
+    s.truncate(10);
+    assert_eq!(s, "aé€");
+    s.truncate(3);
+    assert_eq!(s, "aé");
+    s.truncate(0);
+    assert!(s.is_empty());
+}
+
+#[test]
+fn pop_removes_whole_multibyte_chars() {
+    let mut s = ArrayString::<16>::try_from("a€😀").unwrap();
+    assert_eq!(s.len(), 1 + 3 + 4);
+    assert_eq!(s.pop(), Some('😀'));
+    assert_eq!(s.len(), 4);
+    assert_eq!(s.pop(), Some('€'));
+    assert_eq!(s.as_str(), "a");
+    assert_eq!(s.pop(), Some('a'));
+    assert_eq!(s.pop(), None);
+}
+
+#[test]
+fn try_push_str_never_writes_a_partial_string() {
+    let mut s = ArrayString::<6>::new();
+    s.try_push_str("abc").unwrap();
+    assert_eq!(s.try_push_str("defg").unwrap_err().element(), "defg");
+    assert_eq!(s, "abc");
+    // Fits in bytes left only if it's whole: "é€" is 5 bytes.
+    assert_eq!(s.try_push_str("é€").unwrap_err().element(), "é€");
+    assert_eq!(s, "abc");
+    s.try_push_str("def").unwrap();
+    assert_eq!(s, "abcdef");
+    assert_eq!(s.try_push('x').unwrap_err().element(), 'x');
+
+    // A char whose encoding doesn't fit is refused whole too.
+    let mut s = ArrayString::<3>::try_from("ab").unwrap();
+    assert_eq!(s.try_push('é').unwrap_err().element(), 'é');
+    assert_eq!(s, "ab");
+}
+
+#[test]
+fn write_overflowing_partway_keeps_earlier_pieces() {
+    let mut s = ArrayString::<8>::new();
+    write!(s, "{}-{}", 12, 34).unwrap();
+    assert_eq!(s, "12-34");
+
+    // "abc" fits, then "-" fits, then "defgh" doesn't: the first two
+    // pieces stay, nothing of the third is written.
+    let mut s = ArrayString::<8>::new();
+    s.try_push_str("x").unwrap();
+    let (first, sep, rest) = ("abc", '-', "defgh");
+    assert!(write!(s, "{first}{sep}{rest}").is_err());
+    assert_eq!(s, "xabc-");
+}
+
+#[test]
+fn try_from_hands_input_back() {
+    let err = ArrayString::<4>::try_from("hello").unwrap_err();
+    assert_eq!(err.element(), "hello");
+    assert_eq!(ArrayString::<5>::try_from("hello").unwrap(), "hello");
+    assert_eq!(ArrayString::<0>::try_from("").unwrap(), "");
+}
+
+#[test]
+fn ordering_and_hash_match_str() {
+    let a = ArrayString::<8>::try_from("apple").unwrap();
+    let b = ArrayString::<8>::try_from("banana").unwrap();
+    assert!(a < b);
+    assert_eq!(a.cmp(&a.clone()), std::cmp::Ordering::Equal);
+    assert_eq!(hash_of(&a), hash_of("apple"));
+
+    let mut sorted = [b.clone(), a.clone()];
+    sorted.sort();
+    assert_eq!(sorted, [a, b]);
+}
+
+#[test]
+fn usable_as_map_and_set_key() {
+    let mut map = IndexMap::<ArrayString<8>, u32, 4>::new();
+    map.insert("temp".try_into().unwrap(), 21).unwrap();
+    map.insert("humid".try_into().unwrap(), 40).unwrap();
+    // Looked up by `&str` through `Borrow<str>`.
+    assert_eq!(map.get("temp"), Some(&21));
+    assert_eq!(map.remove("humid"), Some(40));
+    assert!(!map.contains_key("humid"));
+
+    let mut set = IndexSet::<ArrayString<8>, 4>::new();
+    assert_eq!(set.insert("a".try_into().unwrap()), Ok(true));
+    assert_eq!(set.insert("a".try_into().unwrap()), Ok(false));
+    assert!(set.contains("a"));
+}
@@ -0,0 +1,156 @@
+// BinaryHeap ordering for both kinds, checked against a sorted model:
+// pop order, re-sifting through `peek_mut`, `into_sorted_vec`, and the
+// full-heap error.
+use heapless_vector::{BinaryHeap, HeapKind, Max, Min, PeekMut};
+
+const VALUES: [i32; 12] = [5, -3, 17, 0, 5, 42, 8, -11, 23, 8, 1, 9];
+
+fn filled<K: HeapKind>() -> BinaryHeap<i32, 16, K> {
+    let mut heap = BinaryHeap::new();
+    for value in VALUES {
+        heap.try_push(value).unwrap();
+    }
+    heap
+}
+
+fn drain<K: HeapKind>(heap: &mut BinaryHeap<i32, 16, K>) -> Vec<i32> {
+    let mut popped = Vec::new();
+    while let Some(value) = heap.pop() {
+        popped.push(value);
+    }
+    popped
+}
+
+#[test]
+fn pop_order_matches_sorted_model() {
+    let mut ascending = VALUES.to_vec();
+    ascending.sort();
+    let mut descending = ascending.clone();
+    descending.reverse();
+
+    let mut max = filled::<Max>();
+    assert_eq!(max.peek(), Some(&42));
+    assert_eq!(drain(&mut max), descending);
+    assert_eq!(max.pop(), None);
+
+    let mut min = filled::<Min>();
+    assert_eq!(min.peek(), Some(&-11));
+    assert_eq!(drain(&mut min), ascending);
+    assert_eq!(min.pop(), None);
+}
+
+#[test]
+fn pop_order_with_interleaved_pushes() {
+    let mut heap = BinaryHeap::<u32, 8, Min>::new();
+    let mut model = Vec::new();
+    let mut state = 0x9e37_79b9_u32;
+    for _ in 0..500 {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        if !state.is_multiple_of(3) && model.len() < 8 {
+            let value = state % 50;
+            heap.try_push(value).unwrap();
+            model.push(value);
+        } else {
+            model.sort_by(|a, b| b.cmp(a));
+            assert_eq!(heap.pop(), model.pop());
+        }
+        assert_eq!(heap.len(), model.len());
+        assert_eq!(heap.peek(), model.iter().min());
+    }
+}
+
+#[test]
+fn peek_mut_resifts_after_demote_and_promote() {
+    let mut max = filled::<Max>();
+    // Demote the top below everything: it has to sink to the bottom.
+    *max.peek_mut().unwrap() = -100;
+    assert_eq!(max.peek(), Some(&23));
+    // Promote the new top further: it stays on top.
+    *max.peek_mut().unwrap() = 1000;
+    assert_eq!(max.peek(), Some(&1000));
+    let mut expected: Vec<i32> = VALUES
+        .iter()
+        .map(|&v| if v == 42 { -100 } else { v })
+        .collect();
+    expected.retain(|&v| v != 23);
+    expected.push(1000);
+    expected.sort_by(|a, b| b.cmp(a));
+    assert_eq!(drain(&mut max), expected);
+
+    let mut min = filled::<Min>();
+    // For a min-heap, demoting means growing.
+    *min.peek_mut().unwrap() = 100;
+    assert_eq!(min.peek(), Some(&-3));
+    *min.peek_mut().unwrap() = -50;
+    assert_eq!(min.peek(), Some(&-50));
+    let mut expected: Vec<i32> = VALUES
+        .iter()
+        .map(|&v| if v == -11 { 100 } else { v })
+        .collect();
+    expected.retain(|&v| v != -3);
+    expected.push(-50);
+    expected.sort();
+    assert_eq!(drain(&mut min), expected);
+
+    // Looking without changing anything keeps the order.
+    let mut max = filled::<Max>();
+    assert_eq!(*max.peek_mut().unwrap(), 42);
+    assert_eq!(max.peek(), Some(&42));
+    assert!(BinaryHeap::<i32, 4>::new().peek_mut().is_none());
+}
+
+#[test]
+fn peek_mut_pop_removes_the_top() {
+    let mut heap = filled::<Max>();
+    let top = heap.peek_mut().unwrap();
+    assert_eq!(PeekMut::pop(top), 42);
+    assert_eq!(heap.len(), VALUES.len() - 1);
+    assert_eq!(heap.peek(), Some(&23));
+
+    // Popping after a change returns the changed value.
+    let mut top = heap.peek_mut().unwrap();
+    *top = 7;
+    assert_eq!(PeekMut::pop(top), 7);
+    assert_eq!(heap.peek(), Some(&17));
+
+    let mut ascending: Vec<i32> = VALUES.to_vec();
+    ascending.sort();
+    ascending.truncate(VALUES.len() - 2);
+    ascending.reverse();
+    assert_eq!(drain(&mut heap), ascending);
+}
+
+#[test]
+fn into_sorted_vec_direction() {
+    let mut ascending = VALUES.to_vec();
+    ascending.sort();
+    let mut descending = ascending.clone();
+    descending.reverse();
+
+    // The element `pop` would return first ends up last.
+    assert_eq!(filled::<Max>().into_sorted_vec(), ascending[..]);
+    assert_eq!(filled::<Min>().into_sorted_vec(), descending[..]);
+    assert!(BinaryHeap::<i32, 4>::new().into_sorted_vec().is_empty());
+}
+
+#[test]
+fn try_push_on_full_heap_hands_value_back() {
+    let mut heap = BinaryHeap::<i32, 3, Min>::new();
+    for value in [3, 1, 2] {
+        heap.try_push(value).unwrap();
+    }
+    let err = heap.try_push(0).unwrap_err();
+    assert_eq!(err.element(), 0);
+    // The heap is untouched.
+    assert_eq!(heap.len(), 3);
+    assert_eq!(heap.peek(), Some(&1));
+
+    heap.pop();
+    assert_eq!(heap.try_push(0), Ok(()));
+    assert_eq!(heap.peek(), Some(&0));
+
+    let mut empty = BinaryHeap::<i32, 0>::new();
+    assert_eq!(empty.try_push(5).unwrap_err().element(), 5);
+}
@@ -0,0 +1,121 @@
+// IndexMap removal: backward-shift deletion has to keep every key
+// reachable, including along probe chains that wrap past the end of the
+// table, and the last entry moves into the removed one's place. Also
+// IndexSet's insert results.
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use heapless_vector::{IndexMap, IndexSet};
+
+// Hashes a `u32` key to `key / 100`, so a test picks each key's home
+// slot: key 601 lives at home 6 (mod N).
+#[derive(Default)]
+struct HomeHasher(u64);
+
+impl Hasher for HomeHasher {
+    fn finish(&self) -> u64 {
+        self.0 / 100
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 << 8) | u64::from(b);
+        }
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.0 = u64::from(n);
+    }
+}
+
+type Map<const N: usize> = IndexMap<u32, char, N, BuildHasherDefault<HomeHasher>>;
+
+#[test]
+fn backward_shift_across_wrapped_probe_chain() {
+    let mut map = Map::<8>::new();
+    // Homes 6, 6, 6, 7, 0: the chain fills slots 6, 7, 0, 1, 2, so it
+    // wraps around the end of the table.
+    for (key, value) in [(600, 'a'), (601, 'b'), (602, 'c'), (700, 'd'), (1, 'e')] {
+        assert_eq!(map.insert(key, value), Ok(None));
+    }
+
+    // Removing slot 7 has to pull 602 back across the wrap, then 700
+    // and 1 after it.
+    assert_eq!(map.remove(&601), Some('b'));
+    assert_eq!(map.len(), 4);
+    for (key, value) in [(600, 'a'), (602, 'c'), (700, 'd'), (1, 'e')] {
+        assert_eq!(map.get(&key), Some(&value), "key {key}");
+    }
+    assert_eq!(map.get(&601), None);
+    // The last entry took the removed one's place.
+    assert!(
+        map.iter()
+            .eq([(&600, &'a'), (&1, &'e'), (&602, &'c'), (&700, &'d')])
+    );
+
+    // Removing at the head of the chain, then re-inserting, still works.
+    assert_eq!(map.remove(&600), Some('a'));
+    for (key, value) in [(602, 'c'), (700, 'd'), (1, 'e')] {
+        assert_eq!(map.get(&key), Some(&value), "key {key}");
+    }
+    assert_eq!(map.insert(603, 'f'), Ok(None));
+    assert_eq!(map.insert(7, 'g'), Ok(None));
+    for (key, value) in [(602, 'c'), (700, 'd'), (1, 'e'), (603, 'f'), (7, 'g')] {
+        assert_eq!(map.get(&key), Some(&value), "key {key}");
+    }
+}
+
+#[test]
+fn colliding_inserts_and_removes_match_hash_map() {
+    let mut map = Map::<16>::new();
+    let mut model = HashMap::new();
+    // Homes clustered at the end of the table so chains keep wrapping.
+    let keys: Vec<u32> = (0..40).map(|i| (13 + i % 5) % 16 * 100 + i).collect();
+    let mut state = 0x2545_f491_u32;
+    for round in 0..2000 {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        let key = keys[state as usize % keys.len()];
+        if state & 0x100 == 0 {
+            let value = char::from(b'a' + (round % 26) as u8);
+            match map.insert(key, value) {
+                Ok(old) => assert_eq!(old, model.insert(key, value)),
+                Err(err) => {
+                    assert_eq!(model.len(), 16);
+                    assert!(!model.contains_key(&key));
+                    assert_eq!(err.element(), (key, value));
+                }
+            }
+        } else {
+            assert_eq!(map.remove(&key), model.remove(&key));
+        }
+        assert_eq!(map.len(), model.len());
+        for key in &keys {
+            assert_eq!(map.get(key), model.get(key), "key {key}");
+        }
+    }
+}
+
+#[test]
+fn index_set_duplicates_and_full() {
+    let mut set = IndexSet::<&str, 4>::new();
+    for value in ["a", "b", "c"] {
+        assert_eq!(set.insert(value), Ok(true));
+    }
+    assert_eq!(set.insert("b"), Ok(false));
+    assert_eq!(set.insert("d"), Ok(true));
+
+    // Full: a new value comes back, a duplicate is still `Ok(false)`.
+    assert_eq!(set.insert("e").unwrap_err().element(), "e");
+    assert_eq!(set.insert("a"), Ok(false));
+    assert_eq!(set.len(), 4);
+    assert!(!set.contains("e"));
+
+    // Removal moves the last value into the gap.
+    assert!(set.remove("a"));
+    assert!(!set.remove("a"));
+    assert!(set.iter().eq(&["d", "b", "c"]));
+    assert_eq!(set.insert("e"), Ok(true));
+    assert!(set.contains("e"));
+}
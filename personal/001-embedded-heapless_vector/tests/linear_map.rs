@@ -0,0 +1,116 @@
+// LinearMap entry API and ordering, plus LinearSet's insert results.
+use heapless_vector::{Entry, LinearMap, LinearSet};
+
+#[test]
+fn or_insert_on_full_map_hands_value_back() {
+    let mut map = LinearMap::<&str, u32, 2>::new();
+    assert_eq!(map.entry("a").or_insert(1), Ok(&mut 1));
+    *map.entry("b").or_insert_with(|| 2).unwrap() += 10;
+    assert_eq!(map.get("b"), Some(&12));
+
+    // Full: a new key comes back with its value, the map is untouched.
+    assert_eq!(map.entry("c").or_insert(3).unwrap_err().element(), 3);
+    assert_eq!(
+        map.entry("c").or_insert_with(|| 4).unwrap_err().element(),
+        4
+    );
+    assert_eq!(map.len(), 2);
+    assert!(!map.contains_key("c"));
+
+    // An existing key still works when full, and keeps its value.
+    assert_eq!(map.entry("a").or_insert(100), Ok(&mut 1));
+
+    match map.entry("c") {
+        Entry::Vacant(e) => {
+            assert_eq!(*e.key(), "c");
+            assert_eq!(e.insert(3).unwrap_err().element(), 3);
+        }
+        Entry::Occupied(_) => panic!("\"c\" isn't in the map"),
+    }
+    assert_eq!(map.insert("c", 3).unwrap_err().element(), ("c", 3));
+}
+
+#[test]
+fn and_modify_only_touches_occupied_entries() {
+    let mut map = LinearMap::<&str, u32, 4>::new();
+    map.insert("hits", 1).unwrap();
+
+    let hits = map.entry("hits").and_modify(|v| *v += 1).or_insert(0);
+    assert_eq!(hits, Ok(&mut 2));
+
+    // Vacant: the closure doesn't run, the default goes in.
+    let misses = map.entry("misses").and_modify(|v| *v += 1).or_insert(0);
+    assert_eq!(misses, Ok(&mut 0));
+    assert_eq!(map.entry("misses").key(), &"misses");
+    assert!(map.iter().eq([(&"hits", &2), (&"misses", &0)]));
+}
+
+#[test]
+fn occupied_entry_get_insert_and_remove() {
+    let mut map = LinearMap::<&str, u32, 4>::new();
+    for (key, value) in [("a", 1), ("b", 2), ("c", 3)] {
+        map.insert(key, value).unwrap();
+    }
+
+    let Entry::Occupied(mut e) = map.entry("b") else {
+        panic!("\"b\" is in the map");
+    };
+    assert_eq!(*e.key(), "b");
+    assert_eq!(*e.get(), 2);
+    *e.get_mut() = 20;
+    assert_eq!(e.insert(21), 20);
+    assert_eq!(e.remove(), 21);
+    assert!(!map.contains_key("b"));
+    assert_eq!(map.len(), 2);
+
+    let Entry::Occupied(e) = map.entry("a") else {
+        panic!("\"a\" is in the map");
+    };
+    assert_eq!(e.remove_entry(), ("a", 1));
+    assert!(map.iter().eq([(&"c", &3)]));
+}
+
+#[test]
+fn remove_keeps_insertion_order() {
+    let mut map = LinearMap::<u32, char, 5>::new();
+    for (key, value) in [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd'), (5, 'e')] {
+        map.insert(key, value).unwrap();
+    }
+    assert_eq!(map.remove(&2), Some('b'));
+    assert!(map.keys().eq(&[1, 3, 4, 5]));
+
+    // Through an entry, too.
+    if let Entry::Occupied(e) = map.entry(4) {
+        e.remove();
+    }
+    assert!(map.keys().eq(&[1, 3, 5]));
+    assert!(map.values().eq(&['a', 'c', 'e']));
+
+    // New keys go at the end; updating an existing one doesn't move it.
+    map.insert(2, 'B').unwrap();
+    assert_eq!(map.insert(1, 'A'), Ok(Some('a')));
+    assert!(
+        map.iter()
+            .eq([(&1, &'A'), (&3, &'c'), (&5, &'e'), (&2, &'B')])
+    );
+}
+
+#[test]
+fn linear_set_duplicates_and_full() {
+    let mut set = LinearSet::<u32, 3>::new();
+    assert_eq!(set.insert(1), Ok(true));
+    assert_eq!(set.insert(2), Ok(true));
+    assert_eq!(set.insert(1), Ok(false));
+    assert_eq!(set.insert(3), Ok(true));
+
+    // Full: a new value comes back, a duplicate is still `Ok(false)`.
+    assert_eq!(set.insert(4).unwrap_err().element(), 4);
+    assert_eq!(set.insert(2), Ok(false));
+    assert_eq!(set.len(), 3);
+    assert!(!set.contains(&4));
+
+    assert!(set.remove(&2));
+    assert!(!set.remove(&2));
+    assert_eq!(set.insert(4), Ok(true));
+    assert!(set.iter().eq(&[1, 3, 4]));
+}
@@ -0,0 +1,63 @@
+// LruCache recency: `get` promotes, `peek` doesn't, and `put` on a full
+// cache evicts and hands back the least recently used entry.
+use heapless_vector::LruCache;
+
+fn keys<const N: usize>(cache: &LruCache<&'static str, u32, N>) -> Vec<&'static str> {
+    cache.iter().map(|(k, _)| *k).collect()
+}
+
+#[test]
+fn get_promotes_to_most_recently_used() {
+    let mut cache = LruCache::<&str, u32, 3>::new();
+    assert_eq!(cache.put("a", 1), None);
+    assert_eq!(cache.put("b", 2), None);
+    assert_eq!(cache.put("c", 3), None);
+    assert_eq!(keys(&cache), ["c", "b", "a"]);
+
+    assert_eq!(cache.get("a"), Some(&1));
+    assert_eq!(keys(&cache), ["a", "c", "b"]);
+    assert_eq!(cache.peek_lru(), Some((&"b", &2)));
+
+    if let Some(value) = cache.get_mut("b") {
+        *value = 20;
+    }
+    assert_eq!(keys(&cache), ["b", "a", "c"]);
+
+    // `peek` and `contains` leave the order alone.
+    assert_eq!(cache.peek("c"), Some(&3));
+    assert!(cache.contains("c"));
+    assert_eq!(keys(&cache), ["b", "a", "c"]);
+
+    // A miss changes nothing either.
+    assert_eq!(cache.get("z"), None);
+    assert_eq!(keys(&cache), ["b", "a", "c"]);
+}
+
+#[test]
+fn put_on_full_cache_evicts_and_returns_lru() {
+    let mut cache = LruCache::<&str, u32, 3>::new();
+    cache.put("a", 1);
+    cache.put("b", 2);
+    cache.put("c", 3);
+    assert!(cache.is_full());
+
+    assert_eq!(cache.put("d", 4), Some(("a", 1)));
+    assert_eq!(cache.len(), 3);
+    assert!(!cache.contains("a"));
+    assert_eq!(keys(&cache), ["d", "c", "b"]);
+
+    // Promoting "b" makes "c" the one to go next.
+    cache.get("b");
+    assert_eq!(cache.put("e", 5), Some(("c", 3)));
+    assert_eq!(keys(&cache), ["e", "b", "d"]);
+
+    // Updating a cached key returns the old pair and evicts nothing.
+    assert_eq!(cache.put("d", 40), Some(("d", 4)));
+    assert_eq!(cache.len(), 3);
+    assert_eq!(keys(&cache), ["d", "e", "b"]);
+    assert_eq!(cache.peek("d"), Some(&40));
+
+    assert_eq!(cache.pop_lru(), Some(("b", 2)));
+    assert_eq!(cache.put("f", 6), None);
+    assert_eq!(keys(&cache), ["f", "d", "e"]);
+}
@@ -0,0 +1,597 @@
+// Differential tests: random operation sequences are applied to both an
+// ArrayVec and a `Vec` capped at the same capacity (the model), and
+// after every step the two must agree on contents, return values and
+// drop counts. Sequences come from a small seeded PRNG, so a failure
+// prints a seed and a shrunk op list that reproduce it exactly.
+use std::cell::Cell;
+use std::fmt;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::rc::Rc;
+
+use heapless_vector::{ArrayVec, LenType};
+
+const CASES: u64 = 500;
+const MAX_OPS: usize = 64;
+
+// xorshift64*: deterministic and dependency-free. Not for anything but
+// generating test cases.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Zero is a fixed point of xorshift; mix the seed so it isn't.
+        Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // Small values, so `dedup` and `retain` have something to bite on.
+    fn values(&mut self, max_len: usize) -> Vec<u32> {
+        let len = self.below(max_len + 1);
+        (0..len).map(|_| self.below(4) as u32).collect()
+    }
+}
+
+// Index-like fields are reduced modulo the current length when applied,
+// so every op is valid at any point in any sequence, including after
+// shrinking removes the ops before it.
+#[derive(Clone, Debug, PartialEq)]
+enum Op {
+    Push(u32),
+    Pop,
+    Insert(usize, u32),
+    Remove(usize),
+    SwapRemove(usize),
+    Truncate(usize),
+    Clear,
+    Extend(Vec<u32>),
+    TryExtend(Vec<u32>),
+    TryExtendFromSlice(Vec<u32>),
+    Retain(u32),
+    Dedup,
+    Drain {
+        start: usize,
+        len: usize,
+        take: usize,
+    },
+    IntoIter {
+        front: usize,
+        back: usize,
+    },
+}
+
+impl Op {
+    fn random(rng: &mut Rng, cap: usize) -> Op {
+        let span = cap + 2;
+        match rng.below(14) {
+            0 => Op::Push(rng.below(4) as u32),
+            1 => Op::Pop,
+            2 => Op::Insert(rng.below(span), rng.below(4) as u32),
+            3 => Op::Remove(rng.below(span)),
+            4 => Op::SwapRemove(rng.below(span)),
+            5 => Op::Truncate(rng.below(span)),
+            6 => Op::Clear,
+            7 => Op::Extend(rng.values(span)),
+            8 => Op::TryExtend(rng.values(span)),
+            9 => Op::TryExtendFromSlice(rng.values(span)),
+            10 => Op::Retain(1 + rng.below(3) as u32),
+            11 => Op::Dedup,
+            12 => Op::Drain {
+                start: rng.below(span),
+                len: rng.below(span),
+                take: rng.below(span),
+            },
+            _ => Op::IntoIter {
+                front: rng.below(span),
+                back: rng.below(span),
+            },
+        }
+    }
+
+    // Simpler variants of this op, for shrinking: smaller numbers and
+    // shorter lists first.
+    fn simpler(&self) -> Vec<Op> {
+        fn smaller(n: usize) -> Vec<usize> {
+            if n == 0 {
+                vec![]
+            } else {
+                vec![0, n / 2, n - 1]
+            }
+        }
+        fn shorter(values: &[u32]) -> Vec<Vec<u32>> {
+            (0..values.len())
+                .map(|i| [&values[..i], &values[i + 1..]].concat())
+                .collect()
+        }
+        match self {
+            Op::Push(v) if *v > 0 => vec![Op::Push(0)],
+            Op::Insert(i, v) => {
+                let mut out: Vec<Op> = smaller(*i).into_iter().map(|i| Op::Insert(i, *v)).collect();
+                if *v > 0 {
+                    out.push(Op::Insert(*i, 0));
+                }
+                out
+            }
+            Op::Remove(i) => smaller(*i).into_iter().map(Op::Remove).collect(),
+            Op::SwapRemove(i) => smaller(*i).into_iter().map(Op::SwapRemove).collect(),
+            Op::Truncate(n) => smaller(*n).into_iter().map(Op::Truncate).collect(),
+            Op::Extend(vs) => shorter(vs).into_iter().map(Op::Extend).collect(),
+            Op::TryExtend(vs) => shorter(vs).into_iter().map(Op::TryExtend).collect(),
+            Op::TryExtendFromSlice(vs) => shorter(vs)
+                .into_iter()
+                .map(Op::TryExtendFromSlice)
+                .collect(),
+            Op::Drain { start, len, take } => {
+                let (start, len, take) = (*start, *len, *take);
+                let mut out = vec![];
+                out.extend(
+                    smaller(start)
+                        .into_iter()
+                        .map(|start| Op::Drain { start, len, take }),
+                );
+                out.extend(
+                    smaller(len)
+                        .into_iter()
+                        .map(|len| Op::Drain { start, len, take }),
+                );
+                out.extend(
+                    smaller(take)
+                        .into_iter()
+                        .map(|take| Op::Drain { start, len, take }),
+                );
+                out
+            }
+            Op::IntoIter { front, back } => {
+                let (front, back) = (*front, *back);
+                let mut out = vec![];
+                out.extend(
+                    smaller(front)
+                        .into_iter()
+                        .map(|front| Op::IntoIter { front, back }),
+                );
+                out.extend(
+                    smaller(back)
+                        .into_iter()
+                        .map(|back| Op::IntoIter { front, back }),
+                );
+                out
+            }
+            _ => vec![],
+        }
+    }
+}
+
+// Element that counts how many of its kind were created and dropped, so
+// each side can be checked for leaks and double drops.
+#[derive(Default)]
+struct Counts {
+    created: Cell<usize>,
+    dropped: Cell<usize>,
+}
+
+struct Tracked {
+    value: u32,
+    counts: Rc<Counts>,
+}
+
+impl Tracked {
+    fn new(value: u32, counts: &Rc<Counts>) -> Self {
+        counts.created.set(counts.created.get() + 1);
+        Tracked {
+            value,
+            counts: counts.clone(),
+        }
+    }
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        Tracked::new(self.value, &self.counts)
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.counts.dropped.set(self.counts.dropped.get() + 1);
+    }
+}
+
+impl PartialEq for Tracked {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl fmt::Debug for Tracked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+fn values(items: &[Tracked]) -> Vec<u32> {
+    items.iter().map(|item| item.value).collect()
+}
+
+// Fails the current run with a message instead of panicking, so the
+// shrinker can call `run` over and over.
+macro_rules! ensure_eq {
+    ($left:expr, $right:expr, $($ctx:tt)*) => {{
+        let (left, right) = (&$left, &$right);
+        if left != right {
+            return Err(format!(
+                "{}: ArrayVec gave {:?}, model gave {:?}",
+                format_args!($($ctx)*),
+                left,
+                right,
+            ));
+        }
+    }};
+}
+
+// Applies one op to the model: a `Vec` that refuses to grow past `cap`,
+// mirroring ArrayVec's documented behaviour. Returns what an observer
+// would see from the op's return value.
+fn apply_model(model: &mut Vec<Tracked>, cap: usize, op: &Op, counts: &Rc<Counts>) -> Vec<u32> {
+    let len = model.len();
+    match op {
+        Op::Push(v) => {
+            let item = Tracked::new(*v, counts);
+            if len < cap {
+                model.push(item);
+                vec![]
+            } else {
+                vec![item.value]
+            }
+        }
+        Op::Pop => model.pop().map(|item| item.value).into_iter().collect(),
+        Op::Insert(i, v) => {
+            let item = Tracked::new(*v, counts);
+            if len < cap {
+                model.insert(i % (len + 1), item);
+                vec![]
+            } else {
+                vec![item.value]
+            }
+        }
+        Op::Remove(i) if len > 0 => vec![model.remove(i % len).value],
+        Op::SwapRemove(i) if len > 0 => vec![model.swap_remove(i % len).value],
+        Op::Remove(_) | Op::SwapRemove(_) => vec![],
+        Op::Truncate(n) => {
+            model.truncate(*n);
+            vec![]
+        }
+        Op::Clear => {
+            model.clear();
+            vec![]
+        }
+        Op::Extend(vs) => {
+            // Pulls one item past capacity and drops it, like ArrayVec.
+            for item in vs.iter().map(|&v| Tracked::new(v, counts)) {
+                if model.len() == cap {
+                    break;
+                }
+                model.push(item);
+            }
+            vec![]
+        }
+        Op::TryExtend(vs) => {
+            let mut items = vs.iter().map(|&v| Tracked::new(v, counts));
+            for item in items.by_ref() {
+                if model.len() == cap {
+                    let leftover = 1 + items.count();
+                    return vec![leftover as u32];
+                }
+                model.push(item);
+            }
+            vec![]
+        }
+        Op::TryExtendFromSlice(vs) => {
+            let source: Vec<Tracked> = vs.iter().map(|&v| Tracked::new(v, counts)).collect();
+            if len + source.len() > cap {
+                return vec![(len + source.len() - cap) as u32];
+            }
+            model.extend(source.iter().cloned());
+            vec![]
+        }
+        Op::Retain(m) => {
+            model.retain(|item| item.value % m != 0);
+            vec![]
+        }
+        Op::Dedup => {
+            model.dedup();
+            vec![]
+        }
+        Op::Drain {
+            start,
+            len: n,
+            take,
+        } => {
+            let start = start % (len + 1);
+            let end = start + n % (len - start + 1);
+            values(&model.drain(start..end).take(*take).collect::<Vec<_>>())
+        }
+        Op::IntoIter { front, back } => {
+            let mut iter = std::mem::take(model).into_iter();
+            let mut seen: Vec<u32> = iter.by_ref().take(*front).map(|item| item.value).collect();
+            seen.extend(iter.by_ref().rev().take(*back).map(|item| item.value));
+            model.extend(iter);
+            seen
+        }
+    }
+}
+
+// Same op on the ArrayVec side.
+fn apply_vec<const N: usize, L: LenType>(
+    vec: &mut ArrayVec<Tracked, N, L>,
+    op: &Op,
+    counts: &Rc<Counts>,
+) -> Vec<u32> {
+    let len = vec.len();
+    match op {
+        Op::Push(v) => match vec.try_push(Tracked::new(*v, counts)) {
+            Ok(()) => vec![],
+            Err(err) => vec![err.element().value],
+        },
+        Op::Pop => vec.pop().map(|item| item.value).into_iter().collect(),
+        Op::Insert(i, v) => match vec.try_insert(i % (len + 1), Tracked::new(*v, counts)) {
+            Ok(()) => vec![],
+            Err(err) => vec![err.element().value],
+        },
+        Op::Remove(i) if len > 0 => vec![vec.remove(i % len).value],
+        Op::SwapRemove(i) if len > 0 => vec![vec.swap_remove(i % len).value],
+        Op::Remove(_) | Op::SwapRemove(_) => vec![],
+        Op::Truncate(n) => {
+            vec.truncate(*n);
+            vec![]
+        }
+        Op::Clear => {
+            vec.clear();
+            vec![]
+        }
+        Op::Extend(vs) => {
+            vec.extend(vs.iter().map(|&v| Tracked::new(v, counts)));
+            vec![]
+        }
+        Op::TryExtend(vs) => match vec.try_extend(vs.iter().map(|&v| Tracked::new(v, counts))) {
+            Ok(()) => vec![],
+            Err(err) => vec![err.element().count() as u32],
+        },
+        Op::TryExtendFromSlice(vs) => {
+            let source: Vec<Tracked> = vs.iter().map(|&v| Tracked::new(v, counts)).collect();
+            match vec.try_extend_from_slice(&source) {
+                Ok(()) => vec![],
+                Err(err) => vec![err.element() as u32],
+            }
+        }
+        Op::Retain(m) => {
+            vec.retain(|item| item.value % m != 0);
+            vec![]
+        }
+        Op::Dedup => {
+            vec.dedup();
+            vec![]
+        }
+        Op::Drain {
+            start,
+            len: n,
+            take,
+        } => {
+            let start = start % (len + 1);
+            let end = start + n % (len - start + 1);
+            values(&vec.drain(start..end).take(*take).collect::<Vec<_>>())
+        }
+        Op::IntoIter { front, back } => {
+            let mut iter = std::mem::take(vec).into_iter();
+            let mut seen: Vec<u32> = iter.by_ref().take(*front).map(|item| item.value).collect();
+            seen.extend(iter.by_ref().rev().take(*back).map(|item| item.value));
+            *vec = iter.collect();
+            seen
+        }
+    }
+}
+
+// Runs `ops` against both sides, checking after every step. Panics from
+// either side count as failures too. `L` is the ArrayVec's length type.
+fn run<const N: usize, L: LenType>(ops: &[Op]) -> Result<(), String> {
+    let outcome = catch_unwind(AssertUnwindSafe(|| {
+        let vec_counts = Rc::new(Counts::default());
+        let model_counts = Rc::new(Counts::default());
+        let mut vec = ArrayVec::<Tracked, N, L>::new();
+        let mut model = Vec::with_capacity(N);
+
+        for (step, op) in ops.iter().enumerate() {
+            let got = apply_vec(&mut vec, op, &vec_counts);
+            let want = apply_model(&mut model, N, op, &model_counts);
+            ensure_eq!(got, want, "step {step} ({op:?}) returned");
+            ensure_eq!(
+                values(&vec),
+                values(&model),
+                "step {step} ({op:?}) contents"
+            );
+            ensure_eq!(vec.len(), model.len(), "step {step} ({op:?}) len");
+            ensure_eq!(
+                vec.is_full(),
+                model.len() == N,
+                "step {step} ({op:?}) is_full"
+            );
+            ensure_eq!(
+                vec.remaining_capacity(),
+                N - model.len(),
+                "step {step} ({op:?}) remaining_capacity"
+            );
+            ensure_eq!(
+                vec_counts.dropped.get(),
+                model_counts.dropped.get(),
+                "step {step} ({op:?}) drop count"
+            );
+        }
+
+        drop(vec);
+        drop(model);
+        ensure_eq!(
+            vec_counts.dropped.get(),
+            vec_counts.created.get(),
+            "at the end, dropped vs created"
+        );
+        Ok(())
+    }));
+    outcome.unwrap_or_else(|_| Err("panicked".to_string()))
+}
+
+// Greedy shrinking: drop chunks of ops (big chunks first), then try
+// simpler variants of each remaining op, until nothing still fails.
+fn shrink(mut ops: Vec<Op>, fails: impl Fn(&[Op]) -> bool) -> Vec<Op> {
+    loop {
+        let before = ops.clone();
+
+        let mut chunk = ops.len().div_ceil(2);
+        while chunk > 0 {
+            let mut start = 0;
+            while start < ops.len() {
+                let mut candidate = ops.clone();
+                candidate.drain(start..(start + chunk).min(ops.len()));
+                if fails(&candidate) {
+                    ops = candidate;
+                } else {
+                    start += chunk;
+                }
+            }
+            chunk /= 2;
+        }
+
+        for i in 0..ops.len() {
+            for simpler in ops[i].simpler() {
+                let mut candidate = ops.clone();
+                candidate[i] = simpler;
+                if fails(&candidate) {
+                    ops = candidate;
+                    break;
+                }
+            }
+        }
+
+        if ops == before {
+            return ops;
+        }
+    }
+}
+
+fn check<const N: usize, L: LenType>() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let len = rng.below(MAX_OPS + 1);
+        let ops: Vec<Op> = (0..len).map(|_| Op::random(&mut rng, N)).collect();
+
+        if let Err(first) = run::<N, L>(&ops) {
+            let minimal = shrink(ops, |ops| run::<N, L>(ops).is_err());
+            let reason = run::<N, L>(&minimal).unwrap_err();
+            panic!(
+                "N = {N}, L = {}, seed {seed}: {first}\n\
+                 shrunk to {} ops: {minimal:?}\n\
+                 which fails with: {reason}",
+                std::any::type_name::<L>(),
+                minimal.len()
+            );
+        }
+    }
+}
+
+#[test]
+fn matches_vec_model_capacity_0() {
+    check::<0, usize>();
+}
+
+#[test]
+fn matches_vec_model_capacity_1() {
+    check::<1, usize>();
+}
+
+#[test]
+fn matches_vec_model_capacity_4() {
+    check::<4, usize>();
+}
+
+#[test]
+fn matches_vec_model_capacity_16() {
+    check::<16, usize>();
+}
+
+// Smaller length types go through the `LenType` conversions on every
+// length read and write. `N = 255` reaches `u8::MAX` itself, and `300`
+// needs both bytes of a `u16`.
+#[test]
+fn matches_vec_model_u8_len_capacity_4() {
+    check::<4, u8>();
+}
+
+#[test]
+fn matches_vec_model_u8_len_capacity_255() {
+    check::<255, u8>();
+}
+
+#[test]
+fn matches_vec_model_u16_len_capacity_300() {
+    check::<300, u16>();
+}
+
+#[test]
+fn max_length_of_small_len_types() {
+    fn fill<const N: usize, L: LenType>() {
+        let mut vec = ArrayVec::<u32, N, L>::new();
+        for i in 0..N as u32 {
+            vec.try_push(i).unwrap();
+        }
+        assert_eq!(vec.len(), N);
+        assert!(vec.is_full());
+        assert_eq!(vec.remaining_capacity(), 0);
+        assert_eq!(vec.try_push(0).unwrap_err().element(), 0);
+        assert_eq!(vec.last(), Some(&(N as u32 - 1)));
+        assert_eq!(vec.pop(), Some(N as u32 - 1));
+        assert_eq!(vec.len(), N - 1);
+        assert!(vec.iter().copied().eq(0..N as u32 - 1));
+        assert_eq!(vec.clone().into_iter().len(), N - 1);
+    }
+    fill::<255, u8>();
+    fill::<256, u16>();
+    fill::<65535, u16>();
+    fill::<70000, u32>();
+}
+
+#[test]
+fn same_seed_same_ops() {
+    let ops = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..32).map(|_| Op::random(&mut rng, 8)).collect::<Vec<_>>()
+    };
+    assert_eq!(ops(7), ops(7));
+    assert_ne!(ops(7), ops(8));
+}
+
+#[test]
+fn shrinker_finds_minimal_sequence() {
+    // Stand-in bug: "fails" whenever three pushes of a nonzero value
+    // happen. Noise around them should all be shrunk away.
+    let mut rng = Rng::new(1);
+    let mut ops: Vec<Op> = (0..40).map(|_| Op::random(&mut rng, 8)).collect();
+    ops.extend([Op::Push(3), Op::Pop, Op::Push(2), Op::Clear, Op::Push(1)]);
+    let fails = |ops: &[Op]| {
+        ops.iter()
+            .filter(|op| matches!(op, Op::Push(v) if *v > 0))
+            .count()
+            >= 3
+    };
+    assert!(fails(&ops));
+
+    let minimal = shrink(ops, fails);
+    assert_eq!(minimal.len(), 3);
+    assert!(fails(&minimal));
+}
@@ -0,0 +1,315 @@
+// Panic-safety tests for ArrayVec: each one makes a `Drop`, `Clone`,
+// closure or iterator panic partway through an operation, catches the
+// panic, and then checks via a drop log that every element was dropped
+// exactly once (no double drops, no leaks) and that the ArrayVec is
+// left with the documented contents.
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::rc::Rc;
+
+use heapless_vector::ArrayVec;
+
+// Records the id of every element as it's dropped.
+#[derive(Clone, Default)]
+struct DropLog(Rc<RefCell<Vec<u32>>>);
+
+impl DropLog {
+    fn item(&self, id: u32) -> Item {
+        Item {
+            id,
+            log: self.clone(),
+            panic_on_drop: false,
+            panic_on_clone: false,
+        }
+    }
+
+    fn dropped(&self) -> Vec<u32> {
+        let mut ids = self.0.borrow().clone();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+struct Item {
+    id: u32,
+    log: DropLog,
+    panic_on_drop: bool,
+    panic_on_clone: bool,
+}
+
+impl fmt::Debug for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Item({})", self.id)
+    }
+}
+
+impl Item {
+    fn panicking_drop(mut self) -> Self {
+        self.panic_on_drop = true;
+        self
+    }
+
+    fn panicking_clone(mut self) -> Self {
+        self.panic_on_clone = true;
+        self
+    }
+}
+
+// Clones share the id, so a cloned element shows up twice in the log.
+impl Clone for Item {
+    fn clone(&self) -> Self {
+        if self.panic_on_clone {
+            panic!("clone of {} panicked", self.id);
+        }
+        self.log.item(self.id)
+    }
+}
+
+impl Drop for Item {
+    fn drop(&mut self) {
+        self.log.0.borrow_mut().push(self.id);
+        if self.panic_on_drop {
+            panic!("drop of {} panicked", self.id);
+        }
+    }
+}
+
+// Builds `[0, 1, .., len)`, with `panicking` set to panic on drop.
+fn filled<const N: usize>(log: &DropLog, len: u32, panicking: Option<u32>) -> ArrayVec<Item, N> {
+    (0..len)
+        .map(|id| {
+            let item = log.item(id);
+            if Some(id) == panicking {
+                item.panicking_drop()
+            } else {
+                item
+            }
+        })
+        .collect()
+}
+
+fn ids<const N: usize>(vec: &ArrayVec<Item, N>) -> Vec<u32> {
+    vec.iter().map(|item| item.id).collect()
+}
+
+#[test]
+fn drop_keeps_going_after_a_panicking_drop() {
+    let log = DropLog::default();
+    let vec = filled::<4>(&log, 4, Some(1));
+
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(vec))).is_err());
+    assert_eq!(log.dropped(), [0, 1, 2, 3]);
+}
+
+#[test]
+fn truncate_with_panicking_drop() {
+    let log = DropLog::default();
+    let mut vec = filled::<5>(&log, 5, Some(3));
+
+    assert!(catch_unwind(AssertUnwindSafe(|| vec.truncate(1))).is_err());
+    assert_eq!(ids(&vec), [0]);
+    assert_eq!(log.dropped(), [1, 2, 3, 4]);
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn into_iter_drop_with_panicking_drop() {
+    let log = DropLog::default();
+    let mut iter = filled::<4>(&log, 4, Some(2)).into_iter();
+    let first = iter.next().unwrap();
+
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(iter))).is_err());
+    assert_eq!(log.dropped(), [1, 2, 3]);
+
+    drop(first);
+    assert_eq!(log.dropped(), [0, 1, 2, 3]);
+}
+
+#[test]
+fn into_iter_nth_with_panicking_drop() {
+    let log = DropLog::default();
+    let mut iter = filled::<5>(&log, 5, Some(1)).into_iter();
+
+    // Skipping 0..3 drops them; 1 panics, so nothing is returned.
+    assert!(catch_unwind(AssertUnwindSafe(|| iter.nth(3))).is_err());
+    assert_eq!(log.dropped(), [0, 1, 2]);
+    assert_eq!(iter.len(), 2);
+
+    drop(iter);
+    assert_eq!(log.dropped(), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn from_iter_with_panicking_iterator() {
+    let log = DropLog::default();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        (0..5)
+            .map(|id| {
+                assert!(id != 3, "iterator panicked");
+                log.item(id)
+            })
+            .collect::<ArrayVec<Item, 8>>()
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(log.dropped(), [0, 1, 2]);
+}
+
+#[test]
+fn extend_with_panicking_iterator() {
+    let log = DropLog::default();
+    let mut vec = filled::<8>(&log, 2, None);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        vec.extend((2..6).map(|id| {
+            assert!(id != 4, "iterator panicked");
+            log.item(id)
+        }));
+    }));
+    assert!(result.is_err());
+    assert_eq!(ids(&vec), [0, 1, 2, 3]);
+    assert!(log.dropped().is_empty());
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 1, 2, 3]);
+}
+
+#[test]
+fn try_extend_with_panicking_iterator() {
+    let log = DropLog::default();
+    let mut vec = ArrayVec::<Item, 4>::new();
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let _ = vec.try_extend((0..3).map(|id| {
+            assert!(id != 2, "iterator panicked");
+            log.item(id)
+        }));
+    }));
+    assert!(result.is_err());
+    assert_eq!(ids(&vec), [0, 1]);
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 1]);
+}
+
+#[test]
+fn extend_drops_the_rejected_item_once() {
+    let log = DropLog::default();
+    let mut vec = filled::<2>(&log, 2, None);
+
+    // Full after nothing; the one item pulled is dropped, the rest
+    // never leave the iterator.
+    vec.extend((2..5).map(|id| log.item(id)));
+    assert_eq!(log.dropped(), [2]);
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 1, 2]);
+}
+
+#[test]
+fn clone_with_panicking_clone() {
+    let log = DropLog::default();
+    let mut vec = filled::<4>(&log, 2, None);
+    vec.try_push(log.item(2).panicking_clone()).unwrap();
+    vec.try_push(log.item(3)).unwrap();
+
+    // The two clones made before the panic are dropped; the original
+    // is untouched.
+    assert!(catch_unwind(AssertUnwindSafe(|| vec.clone())).is_err());
+    assert_eq!(log.dropped(), [0, 1]);
+    assert_eq!(ids(&vec), [0, 1, 2, 3]);
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 0, 1, 1, 2, 3]);
+}
+
+#[test]
+fn try_extend_from_slice_with_panicking_clone() {
+    let log = DropLog::default();
+    let source = [log.item(10), log.item(11).panicking_clone(), log.item(12)];
+    let mut vec = filled::<8>(&log, 1, None);
+
+    let result = catch_unwind(AssertUnwindSafe(|| vec.try_extend_from_slice(&source)));
+    assert!(result.is_err());
+    assert_eq!(ids(&vec), [0, 10]);
+
+    drop(vec);
+    drop(source);
+    assert_eq!(log.dropped(), [0, 10, 10, 11, 12]);
+}
+
+#[test]
+fn retain_with_panicking_predicate() {
+    let log = DropLog::default();
+    let mut vec = filled::<8>(&log, 6, None);
+
+    // Drops evens until it reaches 3, then panics: 3 and later stay.
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        vec.retain(|item| {
+            assert!(item.id != 3, "predicate panicked");
+            item.id % 2 == 1
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(ids(&vec), [1, 3, 4, 5]);
+    assert_eq!(log.dropped(), [0, 2]);
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn retain_with_panicking_drop() {
+    let log = DropLog::default();
+    let mut vec = filled::<8>(&log, 6, Some(2));
+
+    let result = catch_unwind(AssertUnwindSafe(|| vec.retain(|item| item.id % 2 == 1)));
+    assert!(result.is_err());
+    assert_eq!(ids(&vec), [1, 3, 4, 5]);
+    assert_eq!(log.dropped(), [0, 2]);
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn dedup_by_with_panicking_comparator() {
+    let log = DropLog::default();
+    let mut vec = filled::<8>(&log, 6, None);
+
+    // Pairs (0, 1), (2, 3), (4, 5) are duplicates. Panics on reaching 3,
+    // after dropping 1: 3 and later stay.
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        vec.dedup_by(|cur, prev| {
+            assert!(cur.id != 3, "comparator panicked");
+            cur.id / 2 == prev.id / 2
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(ids(&vec), [0, 2, 3, 4, 5]);
+    assert_eq!(log.dropped(), [1]);
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn drain_drop_with_panicking_drop() {
+    let log = DropLog::default();
+    let mut vec = filled::<8>(&log, 6, Some(2));
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut drain = vec.drain(1..4);
+        drop(drain.next());
+        // Dropping the Drain drops 2 (panics) and 3, then moves 4, 5 back.
+    }));
+    assert!(result.is_err());
+    assert_eq!(ids(&vec), [0, 4, 5]);
+    assert_eq!(log.dropped(), [1, 2, 3]);
+
+    drop(vec);
+    assert_eq!(log.dropped(), [0, 1, 2, 3, 4, 5]);
+}
@@ -0,0 +1,44 @@
+// Slab generational handles: a handle to a removed value must not reach
+// whatever later reuses its slot.
+use heapless_vector::Slab;
+
+#[test]
+fn stale_handle_rejected_after_slot_reuse() {
+    let mut slab = Slab::<String, 4>::new();
+    let keep = slab.try_insert("keep".to_string()).unwrap();
+    let old = slab.try_insert("old".to_string()).unwrap();
+    assert_eq!(slab.remove(old).as_deref(), Some("old"));
+
+    // The freed slot is the head of the free list, so it's reused.
+    let new = slab.try_insert("new".to_string()).unwrap();
+    assert_eq!(new.index(), old.index());
+    assert_ne!(new.generation(), old.generation());
+    assert_ne!(new, old);
+
+    assert!(!slab.contains(old));
+    assert_eq!(slab.get(old), None);
+    assert_eq!(slab.get_mut(old), None);
+    assert_eq!(slab.remove(old), None);
+
+    // The stale handle left the new value alone.
+    assert_eq!(slab.len(), 2);
+    assert!(slab.contains(new));
+    assert_eq!(slab.get(new).map(String::as_str), Some("new"));
+    assert_eq!(slab.get(keep).map(String::as_str), Some("keep"));
+
+    // Still stale after the slot cycles again.
+    assert_eq!(slab.remove(new).as_deref(), Some("new"));
+    let newer = slab.try_insert("newer".to_string()).unwrap();
+    assert_eq!(newer.index(), old.index());
+    for stale in [old, new] {
+        assert!(!slab.contains(stale));
+        assert_eq!(slab.get(stale), None);
+        assert_eq!(slab.remove(stale), None);
+    }
+    assert_eq!(slab.get(newer).map(String::as_str), Some("newer"));
+    assert!(
+        slab.iter()
+            .map(|(h, v)| (h, v.as_str()))
+            .eq([(keep, "keep"), (newer, "newer")])
+    );
+}
@@ -0,0 +1,175 @@
+// SmallVec storage moves: spilling from inline to the heap on push,
+// insert and extend, and moving back with `shrink_to_fit`. Contents and
+// comparisons must not change across either move, and the Vec-like
+// methods (drain, dedup, retain_mut) work the same in both storages.
+#![cfg(feature = "alloc")]
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use heapless_vector::SmallVec;
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn push_spills_to_heap_past_inline_capacity() {
+    let mut vec = SmallVec::<u32, 4>::new();
+    for i in 0..4 {
+        vec.push(i);
+    }
+    assert!(!vec.spilled());
+    assert_eq!(vec.capacity(), 4);
+
+    vec.push(4);
+    assert!(vec.spilled());
+    assert!(vec.capacity() >= 8);
+    assert_eq!(vec, [0, 1, 2, 3, 4]);
+
+    // Popping back under `N` stays on the heap.
+    assert_eq!(vec.pop(), Some(4));
+    assert!(vec.spilled());
+    assert_eq!(vec, [0, 1, 2, 3]);
+}
+
+#[test]
+fn shrink_to_fit_moves_back_inline_only_if_it_fits() {
+    let mut vec: SmallVec<u32, 4> = (0..6).collect();
+    assert!(vec.spilled());
+
+    // Still too long: stays on the heap, with the Vec shrunk.
+    vec.truncate(5);
+    vec.shrink_to_fit();
+    assert!(vec.spilled());
+    assert_eq!(vec.capacity(), 5);
+    assert_eq!(vec, [0, 1, 2, 3, 4]);
+
+    vec.truncate(3);
+    let on_heap = hash_of(&vec);
+    vec.shrink_to_fit();
+    assert!(!vec.spilled());
+    assert_eq!(vec.capacity(), 4);
+    assert_eq!(vec, [0, 1, 2]);
+    assert_eq!(hash_of(&vec), on_heap);
+    assert_eq!(hash_of(&vec), hash_of(&[0u32, 1, 2][..]));
+
+    // Inline already: nothing to do.
+    vec.shrink_to_fit();
+    assert!(!vec.spilled());
+    assert_eq!(vec, [0, 1, 2]);
+}
+
+#[test]
+fn insert_spills_at_the_boundary() {
+    let mut vec: SmallVec<u32, 4> = [0, 1, 3, 4].into();
+    assert!(!vec.spilled());
+    vec.insert(2, 2);
+    assert!(vec.spilled());
+    assert_eq!(vec, [0, 1, 2, 3, 4]);
+
+    // At both ends, once on the heap.
+    vec.insert(0, 9);
+    vec.insert(6, 9);
+    assert_eq!(vec, [9, 0, 1, 2, 3, 4, 9]);
+
+    // One short of full inserts inline.
+    let mut vec: SmallVec<u32, 4> = [0, 1, 2].into_iter().collect();
+    vec.insert(3, 3);
+    assert!(!vec.spilled());
+    assert_eq!(vec, [0, 1, 2, 3]);
+}
+
+#[test]
+fn extend_across_the_boundary_keeps_order() {
+    let mut vec = SmallVec::<u32, 4>::new();
+    vec.extend([0, 1]);
+    assert!(!vec.spilled());
+
+    // Fills inline first, then moves everything to the heap.
+    vec.extend(2..7);
+    assert!(vec.spilled());
+    assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6]);
+
+    let mut vec = SmallVec::<u32, 4>::new();
+    vec.extend_from_slice(&[0, 1, 2]);
+    vec.extend_from_slice(&[3, 4]);
+    assert!(vec.spilled());
+    assert_eq!(vec, [0, 1, 2, 3, 4]);
+
+    // Exactly `N` stays inline.
+    let vec: SmallVec<u32, 4> = (0..4).collect();
+    assert!(!vec.spilled());
+    assert_eq!(vec.into_vec(), [0, 1, 2, 3]);
+}
+
+#[test]
+fn traits_ignore_where_elements_live() {
+    let inline: SmallVec<u32, 8> = (0..5).collect();
+    let heap: SmallVec<u32, 4> = (0..5).collect();
+    assert!(!inline.spilled());
+    assert!(heap.spilled());
+
+    assert_eq!(inline, heap);
+    assert_eq!(hash_of(&inline), hash_of(&heap));
+    assert_eq!(format!("{inline:?}"), format!("{heap:?}"));
+    assert_eq!(format!("{heap:?}"), "[0, 1, 2, 3, 4]");
+    assert_eq!(heap[1..3], [1, 2]);
+
+    let cloned = heap.clone();
+    assert!(cloned.spilled());
+    assert_eq!(cloned, inline);
+}
+
+#[test]
+fn drain_inline_and_spilled() {
+    let mut inline: SmallVec<u32, 8> = (0..6).collect();
+    let drained: Vec<u32> = inline.drain(1..4).collect();
+    assert_eq!(drained, [1, 2, 3]);
+    assert!(!inline.spilled());
+    assert_eq!(inline, [0, 4, 5]);
+
+    let mut heap: SmallVec<u32, 4> = (0..8).collect();
+    let mut drain = heap.drain(2..6);
+    assert_eq!(drain.len(), 4);
+    assert_eq!(drain.next(), Some(2));
+    assert_eq!(drain.next_back(), Some(5));
+    assert_eq!(drain.as_slice(), [3, 4]);
+    // The rest of the range goes when the iterator is dropped.
+    drop(drain);
+    assert!(heap.spilled());
+    assert_eq!(heap, [0, 1, 6, 7]);
+
+    assert_eq!(heap.drain(..).rev().collect::<Vec<_>>(), [7, 6, 1, 0]);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn dedup_family_inline_and_spilled() {
+    let mut inline: SmallVec<u32, 8> = [1, 1, 2, 2, 2, 1].into_iter().collect();
+    inline.dedup();
+    assert_eq!(inline, [1, 2, 1]);
+
+    let mut heap: SmallVec<u32, 2> = [1, 1, 2, 3, 3, 3].into_iter().collect();
+    assert!(heap.spilled());
+    heap.dedup();
+    assert_eq!(heap, [1, 2, 3]);
+
+    let mut heap: SmallVec<u32, 2> = [10, 11, 20, 25, 31].into_iter().collect();
+    heap.dedup_by_key(|v| *v / 10);
+    assert_eq!(heap, [10, 20, 31]);
+
+    let mut inline: SmallVec<u32, 8> = [1, 2, 4, 5, 7].into_iter().collect();
+    // `current` follows `previous` by one: merge into the first of a run.
+    inline.dedup_by(|current, previous| *current == *previous + 1);
+    assert_eq!(inline, [1, 4, 7]);
+
+    let mut heap: SmallVec<u32, 2> = (1..=6).collect();
+    heap.retain_mut(|v| {
+        *v *= 10;
+        *v % 20 == 0
+    });
+    assert_eq!(heap, [20, 40, 60]);
+}
@@ -0,0 +1,72 @@
+// SortedArrayVec merge: the result has to stay sorted by the shared
+// comparator, so binary-search lookups still find every element, with
+// and without overflow.
+use std::cmp::Ordering;
+
+use heapless_vector::{Ascending, Comparator, Descending, SortedArrayVec};
+
+fn assert_sorted<T, const N: usize, C>(vec: &SortedArrayVec<T, N, C>)
+where
+    C: Comparator<T>,
+{
+    for pair in vec.windows(2) {
+        assert_ne!(
+            vec.comparator().compare(&pair[0], &pair[1]),
+            Ordering::Greater
+        );
+    }
+}
+
+fn sorted<const N: usize, C>(values: &[u32]) -> SortedArrayVec<u32, N, C>
+where
+    C: Comparator<u32> + Default,
+{
+    let mut vec = SortedArrayVec::new();
+    for &value in values {
+        vec.try_insert(value).unwrap();
+    }
+    vec
+}
+
+#[test]
+fn merge_keeps_ascending_order_and_lookups() {
+    let left: SortedArrayVec<u32, 8> = sorted(&[1, 9, 4, 4]);
+    let right: SortedArrayVec<u32, 4, Ascending> = sorted(&[5, 2, 4]);
+    let (merged, rest) = left.merge(right);
+    assert!(rest.is_empty());
+    assert_sorted(&merged);
+    assert_eq!(*merged, [1, 2, 4, 4, 4, 5, 9]);
+    for value in [1, 2, 4, 5, 9] {
+        assert!(merged.contains(&value), "{value}");
+    }
+    assert!(!merged.contains(&3));
+}
+
+#[test]
+fn merge_keeps_descending_order_and_lookups() {
+    let left: SortedArrayVec<u32, 4, Descending> = sorted(&[1, 9]);
+    let right: SortedArrayVec<u32, 2, Descending> = sorted(&[5, 2]);
+    let (merged, rest) = left.merge(right);
+    assert!(rest.is_empty());
+    assert_sorted(&merged);
+    assert_eq!(*merged, [9, 5, 2, 1]);
+    for value in [1, 2, 5, 9] {
+        assert!(merged.contains(&value), "{value}");
+    }
+}
+
+#[test]
+fn merge_overflow_is_the_sorted_tail() {
+    let left: SortedArrayVec<u32, 4> = sorted(&[8, 2, 6, 4]);
+    let right: SortedArrayVec<u32, 4> = sorted(&[7, 1, 5, 3]);
+    let (merged, rest) = left.merge(right);
+    assert_sorted(&merged);
+    assert_eq!(*merged, [1, 2, 3, 4]);
+    assert_eq!(rest, [5, 6, 7, 8]);
+    for value in [1, 2, 3, 4] {
+        assert!(merged.contains(&value), "{value}");
+    }
+    for value in rest {
+        assert!(!merged.contains(&value), "{value}");
+    }
+}
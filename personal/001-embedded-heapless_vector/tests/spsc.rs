@@ -0,0 +1,167 @@
+// Stress tests for SpscQueue: the producer and consumer halves run on
+// separate OS threads, so the atomics get exercised on a real host.
+// Waiting sides yield rather than spin, so this stays quick even on a
+// single core.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use heapless_vector::SpscQueue;
+
+const ITEMS: usize = 200_000;
+
+#[test]
+fn fifo_order_across_threads() {
+    let mut queue = SpscQueue::<usize, 16>::new();
+    let (mut producer, mut consumer) = queue.split();
+
+    thread::scope(|s| {
+        s.spawn(move || {
+            for i in 0..ITEMS {
+                let mut item = i;
+                while let Err(err) = producer.try_enqueue(item) {
+                    item = err.element();
+                    thread::yield_now();
+                }
+            }
+        });
+        s.spawn(move || {
+            let mut expected = 0;
+            while expected < ITEMS {
+                match consumer.dequeue() {
+                    Some(item) => {
+                        assert_eq!(item, expected);
+                        expected += 1;
+                    }
+                    None => thread::yield_now(),
+                }
+            }
+            assert!(consumer.dequeue().is_none());
+        });
+    });
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn index_wraparound_with_odd_capacity() {
+    // N = 3 doesn't divide the index space evenly; run far past 2N.
+    let mut queue = SpscQueue::<u32, 3>::new();
+    let (mut producer, mut consumer) = queue.split();
+
+    thread::scope(|s| {
+        s.spawn(move || {
+            for i in 0..ITEMS as u32 {
+                while producer.try_enqueue(i).is_err() {
+                    thread::yield_now();
+                }
+            }
+        });
+        s.spawn(move || {
+            let mut sum = 0u64;
+            let mut received = 0;
+            while received < ITEMS {
+                match consumer.dequeue() {
+                    Some(item) => {
+                        sum += u64::from(item);
+                        received += 1;
+                    }
+                    None => thread::yield_now(),
+                }
+            }
+            let n = ITEMS as u64;
+            assert_eq!(sum, n * (n - 1) / 2);
+        });
+    });
+}
+
+#[test]
+fn capacity_and_peek_single_threaded() {
+    let mut queue = SpscQueue::<char, 2>::new();
+    let (mut producer, mut consumer) = queue.split();
+
+    assert!(consumer.peek().is_none());
+    producer.try_enqueue('a').unwrap();
+    producer.try_enqueue('b').unwrap();
+    assert!(producer.is_full());
+    assert_eq!(producer.try_enqueue('c').unwrap_err().element(), 'c');
+
+    assert_eq!(consumer.peek(), Some(&'a'));
+    assert_eq!(consumer.dequeue(), Some('a'));
+    producer.try_enqueue('c').unwrap();
+    assert_eq!(consumer.len(), 2);
+    assert_eq!(consumer.dequeue(), Some('b'));
+    assert_eq!(consumer.dequeue(), Some('c'));
+    assert!(consumer.dequeue().is_none());
+}
+
+#[test]
+fn zero_capacity_is_always_full() {
+    let mut queue = SpscQueue::<u8, 0>::new();
+    assert!(queue.is_full());
+    assert!(queue.try_enqueue(1).is_err());
+    assert!(queue.dequeue().is_none());
+}
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn drops_items_left_in_queue() {
+    let mut queue = SpscQueue::<Counted, 8>::new();
+    {
+        let (mut producer, mut consumer) = queue.split();
+        thread::scope(|s| {
+            s.spawn(move || {
+                for _ in 0..8 {
+                    assert!(producer.try_enqueue(Counted).is_ok());
+                }
+            });
+        });
+        drop(consumer.dequeue());
+        drop(consumer.dequeue());
+    }
+    assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+    drop(queue);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 8);
+}
+
+#[test]
+fn static_queue_split_across_threads() {
+    // The ISR-to-main-loop setup: the queue lives in a `static` and each
+    // half moves to its own context.
+    static QUEUE: SpscQueue<u32, 4> = SpscQueue::new();
+    let (mut producer, mut consumer) = QUEUE.try_split().unwrap();
+    assert!(QUEUE.try_split().is_none());
+
+    let sender = thread::spawn(move || {
+        for i in 0..ITEMS as u32 {
+            let mut item = i;
+            while let Err(err) = producer.try_enqueue(item) {
+                item = err.element();
+                thread::yield_now();
+            }
+        }
+    });
+    let receiver = thread::spawn(move || {
+        let mut expected = 0;
+        while expected < ITEMS as u32 {
+            match consumer.dequeue() {
+                Some(item) => {
+                    assert_eq!(item, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+    });
+    sender.join().unwrap();
+    receiver.join().unwrap();
+    assert!(QUEUE.is_empty());
+    assert!(QUEUE.try_split().is_none());
+}
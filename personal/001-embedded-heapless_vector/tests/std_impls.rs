@@ -0,0 +1,77 @@
+// Feature-gated impls: `io::Write`/`io::Read` on byte ArrayVecs and the
+// Vec conversions.
+#![cfg(feature = "std")]
+
+use std::io::{ErrorKind, Read, Write};
+
+use heapless_vector::ArrayVec;
+
+#[test]
+fn io_write_is_short_then_write_zero() {
+    let mut buf = ArrayVec::<u8, 8>::new();
+    assert_eq!(buf.write(b"hello").unwrap(), 5);
+    // Only 3 bytes left: a short write, not an error.
+    assert_eq!(buf.write(b"world").unwrap(), 3);
+    assert_eq!(buf, *b"hellowor");
+    // Full: further writes take nothing.
+    assert_eq!(buf.write(b"!").unwrap(), 0);
+    assert_eq!(buf.write(b"").unwrap(), 0);
+    buf.flush().unwrap();
+
+    let err = buf.write_all(b"!").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+    assert_eq!(buf, *b"hellowor");
+
+    // `write_all` keeps what fit before failing.
+    let mut buf = ArrayVec::<u8, 4, u8>::new();
+    assert!(write!(buf, "{}", 123_456).is_err());
+    assert_eq!(buf, *b"1234");
+}
+
+#[test]
+fn io_read_takes_from_the_front() {
+    let mut buf = ArrayVec::<u8, 8>::from(*b"abcdefgh");
+    let mut out = [0; 3];
+    assert_eq!(buf.read(&mut out).unwrap(), 3);
+    assert_eq!(&out, b"abc");
+    // The rest moved down to the start.
+    assert_eq!(buf, *b"defgh");
+
+    // A bigger buffer only gets what's there.
+    let mut out = [0; 8];
+    assert_eq!(buf.read(&mut out).unwrap(), 5);
+    assert_eq!(&out[..5], b"defgh");
+    assert!(buf.is_empty());
+    assert_eq!(buf.read(&mut out).unwrap(), 0);
+
+    let mut buf = ArrayVec::<u8, 8>::from(*b"12345678");
+    let mut text = String::new();
+    buf.read_to_string(&mut text).unwrap();
+    assert_eq!(text, "12345678");
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn array_vec_into_vec() {
+    let arr_vec: ArrayVec<String, 4> = ["a", "b", "c"].map(String::from).into_iter().collect();
+    let vec: Vec<String> = arr_vec.into();
+    assert_eq!(vec, ["a", "b", "c"]);
+
+    let empty: Vec<u32> = ArrayVec::<u32, 4>::new().into();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn try_from_vec_hands_vec_back() {
+    let arr_vec = ArrayVec::<u32, 4>::try_from(vec![1, 2, 3]).unwrap();
+    assert_eq!(arr_vec, [1, 2, 3]);
+    let arr_vec = ArrayVec::<u32, 4, u8>::try_from(vec![1, 2, 3, 4]).unwrap();
+    assert!(arr_vec.is_full());
+
+    // Too long: the same Vec comes back untouched, allocation and all.
+    let vec = vec![1, 2, 3, 4, 5];
+    let ptr = vec.as_ptr();
+    let back = ArrayVec::<u32, 4>::try_from(vec).unwrap_err().element();
+    assert_eq!(back, [1, 2, 3, 4, 5]);
+    assert_eq!(back.as_ptr(), ptr);
+}
@@ -0,0 +1,171 @@
+// Zero-sized element types and zero capacity: both must behave like any
+// other ArrayVec, with capacity checks, iteration counts and drops all
+// going by `len` even though no bytes are ever stored.
+use std::cell::Cell;
+use std::mem::size_of;
+
+use heapless_vector::{ArrayVec, CapacityError};
+
+// Zero-sized, but with a `Drop` we can count. The counter is per thread,
+// and each test runs on its own thread.
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Marker;
+
+impl Drop for Marker {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+fn drops() -> usize {
+    DROPS.with(Cell::get)
+}
+
+#[test]
+fn zst_bounded_counter() {
+    let mut counter = ArrayVec::<(), 3, u8>::new();
+    assert_eq!(size_of::<ArrayVec<(), 3, u8>>(), 1);
+
+    for _ in 0..3 {
+        counter.try_push(()).unwrap();
+    }
+    assert!(counter.is_full());
+    assert_eq!(counter.try_push(()), Err(CapacityError::new(())));
+    assert_eq!(counter.len(), 3);
+    assert_eq!(counter.as_slice().len(), 3);
+
+    assert_eq!(counter.pop(), Some(()));
+    assert_eq!(counter.remaining_capacity(), 1);
+    counter.clear();
+    assert!(counter.is_empty());
+    assert_eq!(counter.pop(), None);
+}
+
+#[test]
+fn zst_iteration_counts() {
+    let vec: ArrayVec<(), 5> = core::iter::repeat_n((), 4).collect();
+    assert_eq!(vec.iter().count(), 4);
+    assert_eq!((&vec).into_iter().len(), 4);
+
+    let mut iter = vec.clone().into_iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next_back(), Some(()));
+    assert_eq!(iter.nth(1), Some(()));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.as_slice().len(), 1);
+    assert_eq!(iter.next(), Some(()));
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(vec.into_iter().rev().count(), 4);
+}
+
+#[test]
+fn zst_drops_once_per_element() {
+    let mut vec = ArrayVec::<Marker, 8>::new();
+    for _ in 0..6 {
+        vec.try_push(Marker).unwrap();
+    }
+    // Rejected pushes drop just the rejected value.
+    let mut full = ArrayVec::<Marker, 0>::new();
+    drop(full.try_push(Marker));
+    assert_eq!(drops(), 1);
+
+    drop(vec.pop());
+    assert_eq!(drops(), 2);
+    vec.truncate(4);
+    assert_eq!(drops(), 3);
+    drop(vec.remove(0));
+    drop(vec.swap_remove(0));
+    assert_eq!(drops(), 5);
+    assert_eq!(vec.len(), 2);
+
+    let copy = vec.clone();
+    drop(copy);
+    assert_eq!(drops(), 7);
+
+    let mut iter = vec.into_iter();
+    drop(iter.next());
+    drop(iter);
+    assert_eq!(drops(), 9);
+}
+
+#[test]
+fn zst_drain_retain_dedup() {
+    let mut vec: ArrayVec<Marker, 8> = core::iter::repeat_n(Marker, 6).collect();
+    let before = drops();
+
+    assert_eq!(vec.drain(1..3).count(), 2);
+    assert_eq!(vec.len(), 4);
+    assert_eq!(drops() - before, 2);
+
+    // `retain` sees every element once.
+    let mut seen = 0;
+    vec.retain(|_| {
+        seen += 1;
+        seen % 2 == 0
+    });
+    assert_eq!(seen, 4);
+    assert_eq!(vec.len(), 2);
+    assert_eq!(drops() - before, 4);
+
+    // All ZST values compare equal, so a run collapses to one.
+    vec.dedup();
+    assert_eq!(vec.len(), 1);
+    assert_eq!(drops() - before, 5);
+}
+
+#[test]
+fn zero_capacity() {
+    let mut vec = ArrayVec::<String, 0>::new();
+    assert_eq!(vec.capacity(), 0);
+    assert!(vec.is_empty() && vec.is_full());
+    assert_eq!(vec.remaining_capacity(), 0);
+
+    let err = vec.try_push("a".to_string()).unwrap_err();
+    assert_eq!(err.element(), "a");
+    assert!(vec.try_insert(0, "b".to_string()).is_err());
+    assert_eq!(vec.pop(), None);
+    assert!(vec.as_slice().is_empty());
+    assert!(vec.as_mut_slice().is_empty());
+    assert!(vec.spare_capacity_mut().is_empty());
+    assert_eq!(vec.drain(..).count(), 0);
+    assert_eq!(
+        vec.try_extend_from_slice(&["c".to_string()]),
+        Err(CapacityError::new(1))
+    );
+    assert!(vec.try_extend_from_slice(&[]).is_ok());
+    assert_eq!(
+        vec.try_extend(["d".to_string()])
+            .unwrap_err()
+            .element()
+            .count(),
+        1
+    );
+
+    vec.extend(["e".to_string()]);
+    assert!(vec.is_empty());
+    assert_eq!(format!("{:?}", vec), "[]");
+    assert_eq!(vec.clone().into_iter().count(), 0);
+
+    let from_array = ArrayVec::<String, 0>::from([]);
+    assert_eq!(from_array, vec);
+    assert!(ArrayVec::<u8, 0>::try_from(&[][..]).is_ok());
+    assert!(ArrayVec::<u8, 0>::try_from(&[1][..]).is_err());
+    let collected: ArrayVec<u8, 0> = (0..10).collect();
+    assert!(collected.is_empty());
+}
+
+#[test]
+fn zero_capacity_zst() {
+    let mut vec = ArrayVec::<Marker, 0>::new();
+    let err = vec.try_push(Marker).unwrap_err();
+    assert_eq!(drops(), 0);
+    drop(err);
+    assert_eq!(drops(), 1);
+    assert_eq!(vec.into_iter().count(), 0);
+    assert_eq!(drops(), 1);
+}